@@ -0,0 +1,85 @@
+use dashmap::DashMap;
+use lsp_types::{Diagnostic, Uri};
+
+/// Tracks the diagnostics currently published for each document URI.
+///
+/// The editor only ever shows the most recent `publishDiagnostics` payload for
+/// a URI, so re-analysing a document simply overwrites its entry here; storing
+/// the latest set lets us clear stale diagnostics explicitly when a document is
+/// closed.
+#[derive(Default)]
+pub struct DiagnosticCollection {
+    diagnostics: DashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl DiagnosticCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the diagnostics stored for `uri`, returning the set that should
+    /// be published.
+    pub fn set(&self, uri: Uri, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        self.diagnostics.insert(uri, diagnostics.clone());
+        diagnostics
+    }
+
+    /// Forget the diagnostics stored for `uri` (e.g. when it is closed).
+    pub fn clear(&self, uri: &Uri) {
+        self.diagnostics.remove(uri);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{DiagnosticSeverity, Position, Range};
+
+    fn diagnostic(message: &str) -> Diagnostic {
+        Diagnostic {
+            range: Range::new(Position::new(0, 0), Position::new(0, 1)),
+            severity: Some(DiagnosticSeverity::ERROR),
+            message: message.to_string(),
+            ..Diagnostic::default()
+        }
+    }
+
+    fn uri(path: &str) -> Uri {
+        format!("file://{path}").parse().unwrap()
+    }
+
+    #[test]
+    fn test_set_returns_the_stored_diagnostics() {
+        let collection = DiagnosticCollection::new();
+        let published = collection.set(uri("/app/x.php"), vec![diagnostic("bad")]);
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].message, "bad");
+    }
+
+    #[test]
+    fn test_set_replaces_previous_diagnostics() {
+        let collection = DiagnosticCollection::new();
+        let document = uri("/app/x.php");
+        collection.set(document.clone(), vec![diagnostic("first")]);
+
+        let published = collection.set(document, vec![diagnostic("second")]);
+
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].message, "second");
+    }
+
+    #[test]
+    fn test_clear_forgets_stored_diagnostics() {
+        let collection = DiagnosticCollection::new();
+        let document = uri("/app/x.php");
+        collection.set(document.clone(), vec![diagnostic("bad")]);
+
+        collection.clear(&document);
+        assert!(collection.diagnostics.get(&document).is_none());
+
+        // Re-setting an empty set after clearing should behave the same as
+        // setting it the first time, rather than merging with stale state.
+        let published = collection.set(document, vec![]);
+        assert!(published.is_empty());
+    }
+}
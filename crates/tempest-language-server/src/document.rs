@@ -1,10 +1,221 @@
-use lsp_types::Uri;
-use tree_sitter::Tree;
+use lsp_types::{Position, TextDocumentContentChangeEvent, Uri};
+use tempest_php_parser::{PhpParser, PhpParserError};
+use tree_sitter::{InputEdit, Node, Point, Tree};
 
-#[allow(dead_code)] // TODO: uri and version are not used yet
+#[allow(dead_code)] // TODO: uri is not used yet
 pub struct Document {
     pub(crate) uri: Uri,
     pub(crate) text: String,
     pub(crate) tree: Tree,
     pub(crate) version: i32,
 }
+
+impl Document {
+    /// Apply a single content change to the document.
+    ///
+    /// Ranged changes are spliced into [`Document::text`] incrementally and the
+    /// previous syntax tree is edited with a [`InputEdit`] before re-parsing, so
+    /// tree-sitter can reuse the unchanged subtrees. A change without a range is
+    /// treated as a full-document replacement.
+    pub fn apply_change(
+        &mut self,
+        parser: &PhpParser,
+        change: &TextDocumentContentChangeEvent,
+    ) -> Result<(), PhpParserError> {
+        let old_tree = match change.range {
+            Some(range) => {
+                let start_byte = offset_at(&self.text, range.start);
+                let old_end_byte = offset_at(&self.text, range.end);
+
+                let start_position = point_at(&self.text, start_byte);
+                let old_end_position = point_at(&self.text, old_end_byte);
+
+                self.text
+                    .replace_range(start_byte..old_end_byte, &change.text);
+
+                let new_end_byte = start_byte + change.text.len();
+                let new_end_position = point_at(&self.text, new_end_byte);
+
+                self.tree.edit(&InputEdit {
+                    start_byte,
+                    old_end_byte,
+                    new_end_byte,
+                    start_position,
+                    old_end_position,
+                    new_end_position,
+                });
+
+                Some(&self.tree)
+            }
+            None => {
+                self.text = change.text.clone();
+                None
+            }
+        };
+
+        self.tree = parser.parse(&self.text, old_tree)?;
+
+        Ok(())
+    }
+
+    /// Byte offset in [`Document::text`] for an LSP position.
+    pub fn byte_offset(&self, position: Position) -> usize {
+        offset_at(&self.text, position)
+    }
+
+    /// The innermost syntax node covering a byte offset.
+    pub fn node_at_offset(&self, offset: usize) -> Option<Node<'_>> {
+        self.tree
+            .root_node()
+            .descendant_for_byte_range(offset, offset)
+    }
+}
+
+/// Convert an LSP [`Position`] (line + UTF-16 code-unit column) into a byte
+/// offset into `text`.
+fn offset_at(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (line_index, line) in text.split_inclusive('\n').enumerate() {
+        if line_index == position.line as usize {
+            return offset + utf16_column_to_byte(line, position.character as usize);
+        }
+        offset += line.len();
+    }
+
+    // Position past the end of the document clamps to the end.
+    text.len()
+}
+
+/// Translate a UTF-16 code-unit column within `line` into a byte offset within
+/// that same line.
+fn utf16_column_to_byte(line: &str, character: usize) -> usize {
+    let mut utf16_units = 0;
+    for (byte_index, ch) in line.char_indices() {
+        if utf16_units >= character {
+            return byte_index;
+        }
+        utf16_units += ch.len_utf16();
+    }
+    line.len()
+}
+
+/// Build the tree-sitter [`Point`] (row + byte column) for a byte offset.
+fn point_at(text: &str, byte_offset: usize) -> Point {
+    let mut row = 0;
+    let mut line_start = 0;
+    for (index, byte) in text.as_bytes().iter().enumerate() {
+        if index >= byte_offset {
+            break;
+        }
+        if *byte == b'\n' {
+            row += 1;
+            line_start = index + 1;
+        }
+    }
+
+    Point {
+        row,
+        column: byte_offset - line_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+
+    fn document(text: &str) -> Document {
+        let parser = PhpParser::new().unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        Document {
+            uri: "file:///scratch.php".parse().unwrap(),
+            text: text.to_string(),
+            tree,
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn test_offset_at_first_line() {
+        let text = "<?php\necho 'hi';\n";
+        assert_eq!(offset_at(text, Position::new(0, 3)), 3);
+    }
+
+    #[test]
+    fn test_offset_at_second_line() {
+        let text = "<?php\necho 'hi';\n";
+        assert_eq!(offset_at(text, Position::new(1, 4)), "<?php\n".len() + 4);
+    }
+
+    #[test]
+    fn test_offset_at_clamps_past_end_of_document() {
+        let text = "<?php\n";
+        assert_eq!(offset_at(text, Position::new(10, 0)), text.len());
+    }
+
+    #[test]
+    fn test_utf16_column_to_byte_ascii() {
+        assert_eq!(utf16_column_to_byte("echo 'hi';", 4), 4);
+    }
+
+    #[test]
+    fn test_utf16_column_to_byte_multibyte() {
+        // 'é' is one UTF-16 code unit but two UTF-8 bytes, so the byte offset
+        // after it must be 2, not 1.
+        let line = "é = 1;";
+        assert_eq!(utf16_column_to_byte(line, 1), 'é'.len_utf8());
+    }
+
+    #[test]
+    fn test_point_at_tracks_row_and_column() {
+        let text = "<?php\necho 'hi';\n";
+        let offset = text.find("echo").unwrap();
+        let point = point_at(text, offset);
+        assert_eq!(point.row, 1);
+        assert_eq!(point.column, 0);
+    }
+
+    #[test]
+    fn test_apply_change_full_replacement() {
+        let parser = PhpParser::new().unwrap();
+        let mut doc = document("<?php\necho 1;\n");
+
+        doc.apply_change(
+            &parser,
+            &TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "<?php\necho 2;\n".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(doc.text, "<?php\necho 2;\n");
+    }
+
+    #[test]
+    fn test_apply_change_ranged_edit_splices_text() {
+        let parser = PhpParser::new().unwrap();
+        let mut doc = document("<?php\necho 1;\n");
+
+        doc.apply_change(
+            &parser,
+            &TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 5), Position::new(1, 6))),
+                range_length: None,
+                text: "2".to_string(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(doc.text, "<?php\necho 2;\n");
+    }
+
+    #[test]
+    fn test_node_at_offset_finds_covering_node() {
+        let doc = document("<?php\necho 'hi';\n");
+        let offset = doc.text.find("'hi'").unwrap();
+        let node = doc.node_at_offset(offset).unwrap();
+        assert!(node.start_byte() <= offset && offset < node.end_byte());
+    }
+}
@@ -1,12 +1,27 @@
+use crate::diagnostics::DiagnosticCollection;
 use crate::document::Document;
-use crate::view_intelligence::ViewIntelligence;
+use crate::template_graph::TemplateGraph;
+use crate::template_index::TemplateIndex;
+use crate::view_intelligence::{RendererRegistry, ViewIntelligence};
 use dashmap::DashMap;
 use lsp_types::{
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, InitializeParams, InitializeResult,
-    InitializedParams, MessageType, ServerCapabilities, ServerInfo, TextDocumentItem,
-    TextDocumentSyncCapability, TextDocumentSyncOptions, Uri,
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, CodeActionResponse, CodeLens, CodeLensOptions, CodeLensParams,
+    Command, CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams,
+    CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentLink, DocumentLinkOptions, DocumentLinkParams,
+    DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse,
+    Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+    InitializedParams, Location, MarkupContent, MarkupKind, MessageType, OneOf, Position, Range,
+    ReferenceParams, ServerCapabilities, ServerInfo, TextDocumentItem, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextDocumentSyncOptions, Uri, WorkspaceEdit,
 };
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tree_sitter::Tree;
 use tempest_php_parser::PhpParser;
 use tower_lsp_server::jsonrpc::Result;
 use tower_lsp_server::{Client, LanguageServer};
@@ -15,6 +30,21 @@ pub struct TempestLanguageServer {
     pub(crate) parser: PhpParser,
     pub(crate) client: Client,
     documents: DashMap<Uri, Document>,
+    diagnostics: Arc<DiagnosticCollection>,
+    /// Latest document version seen per URI, used to supersede in-flight
+    /// analyses so a burst of keystrokes only ever surfaces the final state.
+    versions: Arc<DashMap<Uri, i32>>,
+    /// Reverse index of resolved template files to the `view()` call sites that
+    /// render them, backing "Find All References" from a template.
+    templates: Arc<TemplateIndex>,
+    /// Directed dependency graph between template files, tracking which partials
+    /// each template embeds so edits to a shared partial can be traced back to
+    /// every affected call site.
+    graph: Arc<TemplateGraph>,
+    workspace_root: OnceLock<PathBuf>,
+    /// Method and static selectors treated as view renderers, populated from the
+    /// client's initialization options and falling back to the defaults.
+    renderers: OnceLock<RendererRegistry>,
 }
 
 impl TempestLanguageServer {
@@ -29,9 +59,102 @@ impl TempestLanguageServer {
             parser,
             client,
             documents: DashMap::new(),
+            diagnostics: Arc::new(DiagnosticCollection::new()),
+            versions: Arc::new(DashMap::new()),
+            templates: Arc::new(TemplateIndex::new()),
+            graph: Arc::new(TemplateGraph::new()),
+            workspace_root: OnceLock::new(),
+            renderers: OnceLock::new(),
         }
     }
 
+    /// The workspace root captured during `initialize`, if any.
+    fn workspace_root(&self) -> Option<&Path> {
+        self.workspace_root.get().map(PathBuf::as_path)
+    }
+
+    /// The view-renderer registry negotiated during `initialize`, or the
+    /// built-in defaults when the client sent no configuration.
+    fn renderers(&self) -> RendererRegistry {
+        self.renderers.get().cloned().unwrap_or_default()
+    }
+
+    /// Schedule analysis of a document on a background task.
+    ///
+    /// The task debounces briefly and, before doing any work or publishing,
+    /// checks whether a newer version of the same document has arrived; if so it
+    /// drops the work rather than racing to emit stale output. This keeps the
+    /// server responsive when edits arrive faster than analysis completes.
+    fn schedule_analysis(&self, uri: Uri, version: i32, tree: Tree, text: String) {
+        self.versions.insert(uri.clone(), version);
+
+        let client = self.client.clone();
+        let versions = self.versions.clone();
+        let diagnostics = self.diagnostics.clone();
+        let templates = self.templates.clone();
+        let graph = self.graph.clone();
+        let document_path = uri_to_path(&uri);
+        let workspace_root = self.workspace_root().map(Path::to_path_buf);
+        let renderers = self.renderers();
+
+        tokio::spawn(async move {
+            // Debounce so rapid keystrokes coalesce into a single analysis.
+            tokio::time::sleep(Duration::from_millis(ANALYSIS_DEBOUNCE_MS)).await;
+
+            if is_superseded(&versions, &uri, version) {
+                return;
+            }
+
+            ViewIntelligence::analyze_document(&client, &tree, &text, &uri.to_string(), &renderers)
+                .await;
+
+            if is_superseded(&versions, &uri, version) {
+                return;
+            }
+
+            // Refresh this document's entries in the template reverse index
+            // before computing diagnostics, so the undefined-variable check can
+            // aggregate the view data provided across every call site.
+            if let Some(path) = document_path.as_deref() {
+                let sites = ViewIntelligence::view_render_sites(
+                    &tree,
+                    &text,
+                    path,
+                    workspace_root.as_deref(),
+                    &renderers,
+                );
+                templates.update(&uri, sites);
+
+                // Track the partials this template embeds, so editing a shared
+                // partial can be traced back to every call site that renders it.
+                if is_template_path(path) {
+                    let dependencies = ViewIntelligence::template_dependencies(
+                        &tree,
+                        &text,
+                        path,
+                        workspace_root.as_deref(),
+                    )
+                    .into_iter()
+                    .map(|(_, target)| target)
+                    .collect();
+                    graph.update(path, dependencies);
+                }
+            }
+
+            let computed = ViewIntelligence::diagnostics(
+                &tree,
+                &text,
+                document_path.as_deref(),
+                workspace_root.as_deref(),
+                &renderers,
+                &templates,
+            );
+
+            let computed = diagnostics.set(uri.clone(), computed);
+            client.publish_diagnostics(uri, computed, Some(version)).await;
+        });
+    }
+
     /// Register a document with the server
     ///
     /// This function will parse the document and store it in the server's internal list of documents.
@@ -78,23 +201,75 @@ impl TempestLanguageServer {
             )
             .await;
 
-        // Analyze the document for Tempest view() calls
-        ViewIntelligence::analyze_document(
-            &self.client,
-            &document.tree,
-            &document.text,
-            &text_document.uri.to_string(),
-        )
-        .await;
+        // Analyze the document for Tempest view() calls on a background task.
+        self.schedule_analysis(
+            text_document.uri.clone(),
+            document.version,
+            document.tree.clone(),
+            document.text.clone(),
+        );
 
         self.documents.insert(text_document.uri.clone(), document);
     }
 
+    /// Apply incremental changes to an already-registered document
+    ///
+    /// Each [`TextDocumentContentChangeEvent`] is applied in order to the stored
+    /// [`Document`], reusing the previous syntax tree, and the document is
+    /// re-analyzed once all changes have been spliced in.
+    ///
+    /// [`TextDocumentContentChangeEvent`]: lsp_types::TextDocumentContentChangeEvent
+    pub async fn apply_document_changes(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        let Some(mut document) = self.documents.get_mut(&uri) else {
+            self.client
+                .log_message(
+                    MessageType::WARNING,
+                    format!("Received change for unknown document: {}", *uri),
+                )
+                .await;
+            return;
+        };
+
+        for change in &params.content_changes {
+            if let Err(error) = document.apply_change(&self.parser, change) {
+                self.client
+                    .log_message(
+                        MessageType::ERROR,
+                        format!("Could not apply change to {}: {error}", *uri),
+                    )
+                    .await;
+                return;
+            }
+        }
+
+        document.version = params.text_document.version;
+
+        let version = document.version;
+        let tree = document.tree.clone();
+        let text = document.text.clone();
+        drop(document);
+
+        self.schedule_analysis(uri, version, tree, text);
+    }
+
     /// Unregister a document from the server
     ///
     /// This function will remove a document from the server's internal list of documents.
     pub async fn unregister_document(&self, uri: Uri) {
         self.documents.remove(&uri);
+        self.versions.remove(&uri);
+        self.templates.remove(&uri);
+        if let Some(path) = uri_to_path(&uri) {
+            self.graph.remove(&path);
+        }
+
+        // Drop any diagnostics we had published for this document.
+        self.diagnostics.clear(&uri);
+        self.client
+            .publish_diagnostics(uri.clone(), Vec::new(), None)
+            .await;
 
         self.client
             .log_message(MessageType::INFO, format!("Unregistered document {}", *uri))
@@ -104,7 +279,18 @@ impl TempestLanguageServer {
 
 impl LanguageServer for TempestLanguageServer {
     /// Handle the initialization request
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        if let Some(root) = params.root_uri.as_ref().and_then(uri_to_path) {
+            let _ = self.workspace_root.set(root);
+        }
+
+        if let Some(options) = params.initialization_options.as_ref() {
+            let _ = self
+                .renderers
+                .set(RendererRegistry::from_settings(options));
+        }
+
         Ok(InitializeResult {
             server_info: Some(ServerInfo {
                 name: "Tempest Language Server".to_string(),
@@ -114,9 +300,30 @@ impl LanguageServer for TempestLanguageServer {
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
                         ..TextDocumentSyncOptions::default()
                     },
                 )),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![
+                        "\"".to_string(),
+                        "'".to_string(),
+                        "/".to_string(),
+                    ]),
+                    ..CompletionOptions::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+                document_link_provider: Some(DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default(),
+                }),
                 ..ServerCapabilities::default()
             },
         })
@@ -136,6 +343,14 @@ impl LanguageServer for TempestLanguageServer {
         self.register_document(params.text_document).await;
     }
 
+    /// Handle a text document being changed
+    ///
+    /// This function will be triggered whenever the contents of an open text
+    /// document change in the editor.
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        self.apply_document_changes(params).await;
+    }
+
     /// Handle a text document being closed
     ///
     /// This function will be triggered whenever a text document is closed in the editor.
@@ -143,7 +358,431 @@ impl LanguageServer for TempestLanguageServer {
         self.unregister_document(params.text_document.uri).await;
     }
 
+    /// Offer template-path completions inside a `view()` string argument.
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let offset = document.byte_offset(position);
+        let Some(node) = document.node_at_offset(offset) else {
+            return Ok(None);
+        };
+
+        if !ViewIntelligence::is_in_view_path_argument(node, &document.text) {
+            return Ok(None);
+        }
+
+        let items = ViewIntelligence::path_completions(
+            &document.tree,
+            &document.text,
+            offset,
+            &document_path,
+            self.workspace_root(),
+            &self.renderers(),
+        )
+        .into_iter()
+        .map(|label| {
+            let kind = if label.ends_with('/') {
+                CompletionItemKind::FOLDER
+            } else {
+                CompletionItemKind::FILE
+            };
+            CompletionItem {
+                label,
+                kind: Some(kind),
+                ..CompletionItem::default()
+            }
+        })
+        .collect();
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    /// Resolve a `view()` call's template path to a navigable location.
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let offset = document.byte_offset(position);
+        // A `view()` call resolves to its template; failing that, a partial
+        // reference (`<x-component>` / nested include) inside a template resolves
+        // to the file it embeds.
+        let Some(target) = ViewIntelligence::resolve_definition(
+            &document.tree,
+            &document.text,
+            offset,
+            &document_path,
+            self.workspace_root(),
+            &self.renderers(),
+        )
+        .or_else(|| {
+            ViewIntelligence::resolve_template_dependency(
+                &document.tree,
+                &document.text,
+                offset,
+                &document_path,
+                self.workspace_root(),
+            )
+        }) else {
+            return Ok(None);
+        };
+
+        let Some(target_uri) = path_to_uri(&target) else {
+            return Ok(None);
+        };
+
+        Ok(Some(GotoDefinitionResponse::Scalar(Location {
+            uri: target_uri,
+            range: Range::new(Position::new(0, 0), Position::new(0, 0)),
+        })))
+    }
+
+    /// List the controller call sites that render the template under the cursor.
+    ///
+    /// "Find All References" from inside a `.view.php` template returns every
+    /// `view()` call resolving to that file. For a shared partial the result is
+    /// widened through the dependency graph: the call sites rendering any
+    /// template that transitively embeds the partial are all affected by editing
+    /// it, so they are included too.
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = params.text_document_position.text_document.uri;
+
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let mut locations = self.templates.references(&document_path);
+        for dependent in self.graph.transitive_dependents(&document_path) {
+            locations.extend(self.templates.references(&dependent));
+        }
+        if locations.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(locations))
+    }
+
+    /// Summarize a `view()` call on hover.
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let offset = document.byte_offset(position);
+        let Some(markdown) = ViewIntelligence::hover(
+            &document.tree,
+            &document.text,
+            offset,
+            &document_path,
+            self.workspace_root(),
+            &self.renderers(),
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: markdown,
+            }),
+            range: None,
+        }))
+    }
+
+    /// Offer refactors that normalize how `view()` is imported and called.
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+
+        let actions = ViewIntelligence::code_actions(&document.tree, &document.text, params.range);
+        if actions.is_empty() {
+            return Ok(None);
+        }
+
+        let responses = actions
+            .into_iter()
+            .map(|action| {
+                let mut changes = HashMap::new();
+                changes.insert(uri.clone(), action.edits);
+                CodeActionOrCommand::CodeAction(CodeAction {
+                    title: action.title,
+                    kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(changes),
+                        ..WorkspaceEdit::default()
+                    }),
+                    ..CodeAction::default()
+                })
+            })
+            .collect();
+
+        Ok(Some(responses))
+    }
+
+    /// Surface every `view()` render as a symbol nested under its class/method.
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let uri = params.text_document.uri;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let symbols = ViewIntelligence::document_symbols(
+            &document.tree,
+            &document.text,
+            &document_path,
+            &self.renderers(),
+        );
+        if symbols.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+    }
+
+    /// Render a lens above each `view()` call showing its resolved template and
+    /// how many sibling call sites render the same file.
+    async fn code_lens(&self, params: CodeLensParams) -> Result<Option<Vec<CodeLens>>> {
+        let uri = params.text_document.uri;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let lenses = ViewIntelligence::view_render_lenses(
+            &document.tree,
+            &document.text,
+            &document_path,
+            &self.renderers(),
+        )
+        .into_iter()
+        .map(|lens| {
+            let title = match &lens.target {
+                Some(target) => {
+                    // The reverse index counts this call site too, so discount it
+                    // to report only the *other* renders of the same template.
+                    let siblings = self.templates.references(target).len().saturating_sub(1);
+                    format!("→ {}{}", target.display(), sibling_suffix(siblings))
+                }
+                None => "→ unresolved".to_string(),
+            };
+
+            let command = lens
+                .target
+                .as_ref()
+                .and_then(|target| path_to_uri(target))
+                .map(|target| Command {
+                title: title.clone(),
+                command: "tempest.openTemplate".to_string(),
+                arguments: Some(vec![serde_json::Value::String(target.to_string())]),
+            });
+
+            CodeLens {
+                range: lens.range,
+                command: Some(command.unwrap_or(Command {
+                    title,
+                    command: String::new(),
+                    arguments: None,
+                })),
+                data: None,
+            }
+        })
+        .collect();
+
+        Ok(Some(lenses))
+    }
+
+    /// Expose resolved `view()` template paths as clickable document links.
+    async fn document_link(
+        &self,
+        params: DocumentLinkParams,
+    ) -> Result<Option<Vec<DocumentLink>>> {
+        let uri = params.text_document.uri;
+
+        let Some(document) = self.documents.get(&uri) else {
+            return Ok(None);
+        };
+        let Some(document_path) = uri_to_path(&uri) else {
+            return Ok(None);
+        };
+
+        let links = ViewIntelligence::document_links(
+            &document.tree,
+            &document.text,
+            &document_path,
+            self.workspace_root(),
+            &self.renderers(),
+        )
+        .into_iter()
+        .filter_map(|(range, target)| {
+            path_to_uri(&target).map(|target| DocumentLink {
+                range,
+                target: Some(target),
+                tooltip: None,
+                data: None,
+            })
+        })
+        .collect();
+
+        Ok(Some(links))
+    }
+
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
 }
+
+/// How long to wait before running a scheduled analysis, so bursts of edits
+/// coalesce into a single run.
+const ANALYSIS_DEBOUNCE_MS: u64 = 150;
+
+/// A parenthetical describing how many other call sites render the same
+/// template, or the empty string when this is the only one.
+fn sibling_suffix(siblings: usize) -> String {
+    match siblings {
+        0 => String::new(),
+        1 => " · 1 other render".to_string(),
+        n => format!(" · {n} other renders"),
+    }
+}
+
+/// Whether `path` is a Tempest view template, by its `.view.php` suffix.
+fn is_template_path(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| path.ends_with(".view.php"))
+}
+
+/// Whether a newer version of `uri` has arrived since `version` was scheduled.
+fn is_superseded(versions: &DashMap<Uri, i32>, uri: &Uri, version: i32) -> bool {
+    versions.get(uri).map(|latest| *latest) != Some(version)
+}
+
+/// Convert a `file://` document URI into a filesystem path.
+///
+/// URIs arrive percent-encoded (spaces, unicode, etc.), so the `file://`
+/// prefix is stripped before percent-decoding the remainder.
+fn uri_to_path(uri: &Uri) -> Option<PathBuf> {
+    let encoded = uri.to_string();
+    let encoded = encoded.strip_prefix("file://")?;
+    Some(PathBuf::from(percent_decode(encoded)))
+}
+
+/// Convert a filesystem path into a `file://` URI.
+fn path_to_uri(path: &Path) -> Option<Uri> {
+    format!("file://{}", percent_encode(&path.display().to_string()))
+        .parse()
+        .ok()
+}
+
+/// Percent-decode a URI component, e.g. `%20` back into a space.
+fn percent_decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|pair| std::str::from_utf8(pair).ok())
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok());
+            if let Some(byte) = hex {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Percent-encode a filesystem path for embedding in a `file://` URI,
+/// leaving path separators and unreserved characters untouched.
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod uri_conversion_tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_to_path_decodes_percent_encoded_space() {
+        let uri: Uri = "file:///home/user/My%20Project/x.view.php".parse().unwrap();
+        assert_eq!(
+            uri_to_path(&uri),
+            Some(PathBuf::from("/home/user/My Project/x.view.php"))
+        );
+    }
+
+    #[test]
+    fn test_uri_to_path_decodes_percent_encoded_unicode() {
+        let uri: Uri = "file:///home/user/caf%C3%A9/x.view.php".parse().unwrap();
+        assert_eq!(
+            uri_to_path(&uri),
+            Some(PathBuf::from("/home/user/café/x.view.php"))
+        );
+    }
+
+    #[test]
+    fn test_path_to_uri_encodes_space() {
+        let uri = path_to_uri(Path::new("/home/user/My Project/x.view.php")).unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "file:///home/user/My%20Project/x.view.php"
+        );
+    }
+
+    #[test]
+    fn test_uri_path_round_trip() {
+        let path = Path::new("/home/user/My Project/café/x.view.php");
+        let uri = path_to_uri(path).unwrap();
+        assert_eq!(uri_to_path(&uri), Some(path.to_path_buf()));
+    }
+}
@@ -1,5 +1,8 @@
+mod diagnostics;
 mod document;
 mod language_server;
+mod template_graph;
+mod template_index;
 mod view_intelligence;
 
 use crate::language_server::TempestLanguageServer;
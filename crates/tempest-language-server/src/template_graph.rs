@@ -0,0 +1,174 @@
+use dashmap::DashMap;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Directed dependency graph between template files.
+///
+/// An edge `a -> b` means the template `a` embeds template `b`, either through a
+/// PHP-level `view(...)`/`include` call or an `<x-component>` tag. The graph is
+/// maintained incrementally alongside the template reverse index: analyzing a
+/// template replaces the outgoing edges it previously contributed, so the graph
+/// always reflects the set of currently open templates.
+///
+/// Both directions are kept so that, given a shared partial, the transitive set
+/// of templates that embed it can be walked without scanning the whole graph.
+/// Traversal records visited nodes, so cycles between partials terminate rather
+/// than looping forever.
+#[derive(Default)]
+pub struct TemplateGraph {
+    /// Each template mapped to the templates it embeds.
+    embeds: DashMap<PathBuf, Vec<PathBuf>>,
+    /// Each template mapped to the templates that embed it (reverse of `embeds`).
+    embedded_by: DashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl TemplateGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the outgoing edges of `template` with `dependencies`.
+    pub fn update(&self, template: &Path, dependencies: Vec<PathBuf>) {
+        self.remove(template);
+
+        let mut deduped = Vec::new();
+        for dependency in dependencies {
+            if dependency == template || deduped.contains(&dependency) {
+                // A template embedding itself contributes no useful edge and
+                // would only seed a trivial cycle.
+                continue;
+            }
+            self.embedded_by
+                .entry(dependency.clone())
+                .or_default()
+                .push(template.to_path_buf());
+            deduped.push(dependency);
+        }
+
+        if !deduped.is_empty() {
+            self.embeds.insert(template.to_path_buf(), deduped);
+        }
+    }
+
+    /// Forget every edge originating at `template`.
+    pub fn remove(&self, template: &Path) {
+        let Some((_, dependencies)) = self.embeds.remove(template) else {
+            return;
+        };
+
+        for dependency in dependencies {
+            if let Some(mut parents) = self.embedded_by.get_mut(&dependency) {
+                parents.retain(|parent| parent != template);
+            }
+            self.embedded_by
+                .remove_if(&dependency, |_, parents| parents.is_empty());
+        }
+    }
+
+    /// Every template that embeds `template`, directly or transitively.
+    ///
+    /// The returned set does not include `template` itself. Traversal tracks the
+    /// templates already seen, so cycles between partials are broken and the walk
+    /// always terminates.
+    pub fn transitive_dependents(&self, template: &Path) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        seen.insert(template.to_path_buf());
+        let mut queue = VecDeque::new();
+        queue.push_back(template.to_path_buf());
+
+        let mut dependents = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            let Some(parents) = self.embedded_by.get(&current) else {
+                continue;
+            };
+            for parent in parents.iter() {
+                if seen.insert(parent.clone()) {
+                    dependents.push(parent.clone());
+                    queue.push_back(parent.clone());
+                }
+            }
+        }
+
+        dependents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitive_dependents_follows_chain() {
+        let graph = TemplateGraph::new();
+        let layout = PathBuf::from("/templates/layout.view.php");
+        let page = PathBuf::from("/templates/page.view.php");
+        let partial = PathBuf::from("/templates/partial.view.php");
+
+        graph.update(&page, vec![partial.clone()]);
+        graph.update(&layout, vec![page.clone()]);
+
+        let mut dependents = graph.transitive_dependents(&partial);
+        dependents.sort();
+        let mut expected = vec![page.clone(), layout.clone()];
+        expected.sort();
+        assert_eq!(dependents, expected);
+    }
+
+    #[test]
+    fn test_transitive_dependents_excludes_self() {
+        let graph = TemplateGraph::new();
+        let template = PathBuf::from("/templates/solo.view.php");
+
+        assert!(graph.transitive_dependents(&template).is_empty());
+    }
+
+    #[test]
+    fn test_transitive_dependents_breaks_cycles() {
+        let graph = TemplateGraph::new();
+        let a = PathBuf::from("/templates/a.view.php");
+        let b = PathBuf::from("/templates/b.view.php");
+
+        graph.update(&a, vec![b.clone()]);
+        graph.update(&b, vec![a.clone()]);
+
+        let dependents = graph.transitive_dependents(&a);
+        assert_eq!(dependents, vec![b.clone()]);
+    }
+
+    #[test]
+    fn test_update_replaces_previous_edges() {
+        let graph = TemplateGraph::new();
+        let page = PathBuf::from("/templates/page.view.php");
+        let old_partial = PathBuf::from("/templates/old.view.php");
+        let new_partial = PathBuf::from("/templates/new.view.php");
+
+        graph.update(&page, vec![old_partial.clone()]);
+        assert_eq!(graph.transitive_dependents(&old_partial), vec![page.clone()]);
+
+        graph.update(&page, vec![new_partial.clone()]);
+        assert!(graph.transitive_dependents(&old_partial).is_empty());
+        assert_eq!(graph.transitive_dependents(&new_partial), vec![page.clone()]);
+    }
+
+    #[test]
+    fn test_remove_forgets_outgoing_edges() {
+        let graph = TemplateGraph::new();
+        let page = PathBuf::from("/templates/page.view.php");
+        let partial = PathBuf::from("/templates/partial.view.php");
+
+        graph.update(&page, vec![partial.clone()]);
+        graph.remove(&page);
+
+        assert!(graph.transitive_dependents(&partial).is_empty());
+    }
+
+    #[test]
+    fn test_self_embed_is_ignored() {
+        let graph = TemplateGraph::new();
+        let template = PathBuf::from("/templates/recursive.view.php");
+
+        graph.update(&template, vec![template.clone()]);
+
+        assert!(graph.transitive_dependents(&template).is_empty());
+    }
+}
@@ -0,0 +1,233 @@
+use dashmap::DashMap;
+use lsp_types::{Location, Range, Uri};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// The view data a single `view()` call statically provides to its template.
+#[derive(Clone, Default)]
+pub struct ProvidedData {
+    /// The data keys the call passes, when they can be enumerated.
+    pub keys: Vec<String>,
+    /// Whether every key the call provides could be determined statically. A
+    /// call that spreads an array, computes a key, passes a non-array data
+    /// argument, or attaches data only through fluent setters / controller
+    /// context (no statically-enumerable data source) is not enumerable.
+    pub enumerable: bool,
+}
+
+/// A single place a template is rendered from.
+#[derive(Clone)]
+struct CallSite {
+    uri: Uri,
+    range: Range,
+    data: ProvidedData,
+}
+
+/// Workspace-wide reverse index mapping each resolved template file to the
+/// `view()` call sites that render it.
+///
+/// The index is maintained incrementally: analyzing a document replaces the
+/// call sites it previously contributed, so the map always reflects the set of
+/// currently open documents. Because it is fed from the resolved links produced
+/// by the call analysis, templates referenced through `view`, an aliased
+/// `render`, or the fully-qualified `\Tempest\view` all land under the same
+/// template key.
+#[derive(Default)]
+pub struct TemplateIndex {
+    by_template: DashMap<PathBuf, Vec<CallSite>>,
+    by_document: DashMap<Uri, Vec<PathBuf>>,
+}
+
+impl TemplateIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the call sites contributed by `uri`.
+    ///
+    /// Each entry of `sites` pairs the resolved template path with the range of
+    /// the rendering call in `uri` and the data that call provides.
+    pub fn update(&self, uri: &Uri, sites: Vec<(PathBuf, Range, ProvidedData)>) {
+        self.remove(uri);
+
+        let mut templates = Vec::new();
+        for (template, range, data) in sites {
+            self.by_template
+                .entry(template.clone())
+                .or_default()
+                .push(CallSite {
+                    uri: uri.clone(),
+                    range,
+                    data,
+                });
+            if !templates.contains(&template) {
+                templates.push(template);
+            }
+        }
+
+        if !templates.is_empty() {
+            self.by_document.insert(uri.clone(), templates);
+        }
+    }
+
+    /// Forget every call site contributed by `uri`.
+    pub fn remove(&self, uri: &Uri) {
+        let Some((_, templates)) = self.by_document.remove(uri) else {
+            return;
+        };
+
+        for template in templates {
+            if let Some(mut sites) = self.by_template.get_mut(&template) {
+                sites.retain(|site| &site.uri != uri);
+            }
+            // Drop the template entry once nothing renders it any more.
+            self.by_template.remove_if(&template, |_, sites| sites.is_empty());
+        }
+    }
+
+    /// The union of data keys provided across every call site rendering
+    /// `template`.
+    ///
+    /// Returns `None` when the full set can't be determined — either no known
+    /// call site renders the template, or some call site provides data that
+    /// can't be enumerated statically. Callers treat `None` as "a key could be
+    /// supplied invisibly", suppressing the undefined-variable check, so a
+    /// variable is only ever flagged when no call site anywhere supplies it.
+    pub fn provided_keys(&self, template: &Path) -> Option<HashSet<String>> {
+        let sites = self.by_template.get(template)?;
+        if sites.is_empty() {
+            return None;
+        }
+
+        let mut union = HashSet::new();
+        for site in sites.iter() {
+            if !site.data.enumerable {
+                return None;
+            }
+            union.extend(site.data.keys.iter().cloned());
+        }
+        Some(union)
+    }
+
+    /// Every call site rendering `template`, as LSP locations.
+    pub fn references(&self, template: &Path) -> Vec<Location> {
+        self.by_template
+            .get(template)
+            .map(|sites| {
+                sites
+                    .iter()
+                    .map(|site| Location {
+                        uri: site.uri.clone(),
+                        range: site.range,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(path: &str) -> Uri {
+        format!("file://{path}").parse().unwrap()
+    }
+
+    fn enumerable(keys: &[&str]) -> ProvidedData {
+        ProvidedData {
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            enumerable: true,
+        }
+    }
+
+    #[test]
+    fn test_provided_keys_unions_across_call_sites() {
+        let index = TemplateIndex::new();
+        let template = PathBuf::from("/templates/home.view.php");
+
+        index.update(
+            &uri("/app/a.php"),
+            vec![(template.clone(), Range::default(), enumerable(&["title"]))],
+        );
+        index.update(
+            &uri("/app/b.php"),
+            vec![(template.clone(), Range::default(), enumerable(&["subtitle"]))],
+        );
+
+        let keys = index.provided_keys(&template).unwrap();
+        assert_eq!(
+            keys,
+            HashSet::from(["title".to_string(), "subtitle".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_provided_keys_none_when_no_call_site_renders_it() {
+        let index = TemplateIndex::new();
+        let template = PathBuf::from("/templates/orphan.view.php");
+
+        assert!(index.provided_keys(&template).is_none());
+    }
+
+    #[test]
+    fn test_provided_keys_none_when_any_site_is_not_enumerable() {
+        let index = TemplateIndex::new();
+        let template = PathBuf::from("/templates/home.view.php");
+
+        index.update(
+            &uri("/app/a.php"),
+            vec![(template.clone(), Range::default(), enumerable(&["title"]))],
+        );
+        index.update(
+            &uri("/app/b.php"),
+            vec![(
+                template.clone(),
+                Range::default(),
+                ProvidedData {
+                    keys: Vec::new(),
+                    enumerable: false,
+                },
+            )],
+        );
+
+        assert!(index.provided_keys(&template).is_none());
+    }
+
+    #[test]
+    fn test_update_replaces_previous_call_sites_for_a_document() {
+        let index = TemplateIndex::new();
+        let document = uri("/app/a.php");
+        let old_template = PathBuf::from("/templates/old.view.php");
+        let new_template = PathBuf::from("/templates/new.view.php");
+
+        index.update(
+            &document,
+            vec![(old_template.clone(), Range::default(), enumerable(&[]))],
+        );
+        index.update(
+            &document,
+            vec![(new_template.clone(), Range::default(), enumerable(&[]))],
+        );
+
+        assert!(index.references(&old_template).is_empty());
+        assert_eq!(index.references(&new_template).len(), 1);
+    }
+
+    #[test]
+    fn test_references_lists_every_call_site() {
+        let index = TemplateIndex::new();
+        let template = PathBuf::from("/templates/home.view.php");
+
+        index.update(
+            &uri("/app/a.php"),
+            vec![(template.clone(), Range::default(), enumerable(&[]))],
+        );
+        index.update(
+            &uri("/app/b.php"),
+            vec![(template.clone(), Range::default(), enumerable(&[]))],
+        );
+
+        assert_eq!(index.references(&template).len(), 2);
+    }
+}
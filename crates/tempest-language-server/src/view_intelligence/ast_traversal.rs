@@ -0,0 +1,78 @@
+use crate::view_intelligence::types::{Result, ViewAnalysisError};
+use lsp_types::{Position, Range};
+use tree_sitter::Node;
+
+pub struct AstTraversal;
+
+impl AstTraversal {
+    pub fn extract_node_text(node: &Node, text: &str) -> Result<String> {
+        node.utf8_text(text.as_bytes())
+            .map(|s| s.to_string())
+            .map_err(|_| ViewAnalysisError::TextExtractionError)
+    }
+
+    pub fn traverse_children<F>(node: &Node, mut callback: F)
+    where
+        F: FnMut(&Node),
+    {
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            loop {
+                callback(&cursor.node());
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Build an [`lsp_types::Range`] spanning the given tree-sitter node.
+    pub fn node_range(node: &Node) -> Range {
+        let start = node.start_position();
+        let end = node.end_position();
+        Range {
+            start: Position {
+                line: start.row as u32,
+                character: start.column as u32,
+            },
+            end: Position {
+                line: end.row as u32,
+                character: end.column as u32,
+            },
+        }
+    }
+
+    pub fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            loop {
+                let child = cursor.node();
+                if child.kind() == kind {
+                    return Some(child);
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+        None
+    }
+
+    /// Every direct child of `node` with the given kind, in source order.
+    pub fn find_children_by_kind<'a>(node: &'a Node, kind: &str) -> Vec<Node<'a>> {
+        let mut children = Vec::new();
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            loop {
+                let child = cursor.node();
+                if child.kind() == kind {
+                    children.push(child);
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+        children
+    }
+}
@@ -1,49 +1,80 @@
 use crate::view_intelligence::ast_traversal::AstTraversal;
-use crate::view_intelligence::types::{Result, ViewAnalysisError, ViewCall, ViewParameter};
+use crate::view_intelligence::helpers::path_argument_node;
+use crate::view_intelligence::path_resolver::TemplatePathResolver;
+use crate::view_intelligence::query::{CapturedCall, ViewQuery};
+use crate::view_intelligence::types::{Result, ResolvedPath, ViewAnalysisError, ViewCall, ViewParameter};
+use std::path::Path;
 use tree_sitter::{Node, Tree};
 
 pub struct FunctionCallAnalyzer;
 
 impl FunctionCallAnalyzer {
     pub fn find_function_calls(tree: &Tree, text: &str) -> Result<Vec<ViewCall>> {
-        let call_nodes = AstTraversal::find_nodes_by_kind(tree, "function_call_expression");
-        let mut calls = Vec::new();
+        Self::find_function_calls_from(&ViewQuery::run(tree, text).calls, text, None)
+    }
 
-        for node in call_nodes {
-            if let Ok(view_call) = Self::extract_view_call_info(&node, text) {
-                calls.push(view_call);
+    /// Build the call list from the calls already captured by [`ViewQuery`],
+    /// reusing the `function` and `arguments` sub-nodes gathered in the same
+    /// match instead of descending into each call again.
+    ///
+    /// `document_path` is the filesystem path of the file being analyzed, used
+    /// to fold `__DIR__`/`__FILE__` when evaluating each call's template path.
+    pub fn find_function_calls_from(
+        calls: &[CapturedCall],
+        text: &str,
+        document_path: Option<&Path>,
+    ) -> Result<Vec<ViewCall>> {
+        let mut view_calls = Vec::new();
+
+        for call in calls {
+            if let Ok(view_call) = Self::extract_view_call_info(call, text, document_path) {
+                view_calls.push(view_call);
             }
         }
 
-        Ok(calls)
+        Ok(view_calls)
     }
 
-    fn extract_view_call_info(node: &Node, text: &str) -> Result<ViewCall> {
-        let function_node =
-            node.child_by_field_name("function")
-                .ok_or(ViewAnalysisError::ParseError(
-                    "Function call missing function field".to_string(),
-                ))?;
+    pub fn extract_view_call_info(
+        call: &CapturedCall,
+        text: &str,
+        document_path: Option<&Path>,
+    ) -> Result<ViewCall> {
+        let function_node = call
+            .function
+            .ok_or(ViewAnalysisError::ParseError(
+                "Function call missing function field".to_string(),
+            ))?;
 
         let function_name = AstTraversal::extract_node_text(&function_node, text)?;
-        let line = node.start_position().row + 1;
-        let call_text = AstTraversal::extract_node_text(node, text)?;
+        let line = call.node.start_position().row + 1;
+        let call_text = AstTraversal::extract_node_text(&call.node, text)?;
+
+        let parameters = Self::parse_function_parameters(call.arguments.as_ref(), text)?;
 
-        let parameters = Self::parse_function_parameters(node, text)?;
+        let resolved_path = path_argument_node(call.arguments)
+            .map(|argument| TemplatePathResolver::evaluate(&argument, text, document_path))
+            .unwrap_or(ResolvedPath::Unresolvable { prefix: None });
 
         Ok(ViewCall::with_parameters(
             function_name,
             line,
             call_text,
             parameters,
+            AstTraversal::node_range(&call.node),
+            AstTraversal::node_range(&function_node),
+            resolved_path,
         ))
     }
 
-    fn parse_function_parameters(node: &Node, text: &str) -> Result<Vec<ViewParameter>> {
+    fn parse_function_parameters(
+        arguments_node: Option<&Node>,
+        text: &str,
+    ) -> Result<Vec<ViewParameter>> {
         let mut parameters = Vec::new();
 
-        if let Some(arguments_node) = node.child_by_field_name("arguments") {
-            AstTraversal::traverse_children(&arguments_node, |child| {
+        if let Some(arguments_node) = arguments_node {
+            AstTraversal::traverse_children(arguments_node, |child| {
                 if child.kind() == "argument" {
                     if let Ok(param) = Self::parse_single_argument(child, text) {
                         parameters.push(param);
@@ -57,6 +88,7 @@ impl FunctionCallAnalyzer {
 
     fn parse_single_argument(node: &Node, text: &str) -> Result<ViewParameter> {
         let raw_text = AstTraversal::extract_node_text(node, text)?;
+        let range = AstTraversal::node_range(node);
 
         if let Some(name_node) = node.child_by_field_name("name") {
             let name = AstTraversal::extract_node_text(&name_node, text)?;
@@ -87,6 +119,7 @@ impl FunctionCallAnalyzer {
                 name: Some(name),
                 value,
                 raw_text,
+                range,
             });
         }
 
@@ -95,6 +128,7 @@ impl FunctionCallAnalyzer {
             name: None,
             value,
             raw_text,
+            range,
         })
     }
 }
@@ -0,0 +1,212 @@
+use lsp_types::{Position, Range};
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::{Node, Tree};
+
+/// The node of a call's template-path argument: its first argument, whether
+/// positional or named `path:`. Arguments are matched by position only, so when
+/// other named arguments come before `path:`, the first of them is returned.
+pub fn path_argument_node<'a>(arguments: Option<Node<'a>>) -> Option<Node<'a>> {
+    let arguments = arguments?;
+    let mut cursor = arguments.walk();
+    if cursor.goto_first_child() {
+        loop {
+            let child = cursor.node();
+            if child.kind() == "argument" {
+                return Some(child);
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// The resolved template path as a symbol label.
+pub fn template_label(path: &Path) -> String {
+    path.display().to_string()
+}
+
+/// Whether `position` falls within `range` (inclusive).
+pub fn position_within(position: Position, range: Range) -> bool {
+    let after_start = (range.start.line, range.start.character) <= (position.line, position.character);
+    let before_end = (position.line, position.character) <= (range.end.line, range.end.character);
+    after_start && before_end
+}
+
+/// The LSP [`Position`] of a byte offset into `text`, counting UTF-16 code
+/// units per the LSP position encoding.
+pub fn position_at(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let mut line = 0u32;
+    let mut line_start = 0usize;
+    for (index, byte) in text.as_bytes()[..offset].iter().enumerate() {
+        if *byte == b'\n' {
+            line += 1;
+            line_start = index + 1;
+        }
+    }
+    let character = text[line_start..offset].encode_utf16().count() as u32;
+    Position::new(line, character)
+}
+
+/// The end of a node as an LSP [`Position`].
+pub fn end_position(node: &Node) -> Position {
+    let end = node.end_position();
+    Position {
+        line: end.row as u32,
+        character: end.column as u32,
+    }
+}
+
+/// A zero-width range at `position`, for pure insertions.
+pub fn zero_width(position: Position) -> Range {
+    Range::new(position, position)
+}
+
+/// Whether `kind` is one of the call-expression node kinds a view render can
+/// take: a free function, a method call, or a static call.
+pub fn is_call_expression(kind: &str) -> bool {
+    matches!(
+        kind,
+        "function_call_expression" | "member_call_expression" | "scoped_call_expression"
+    )
+}
+
+/// Climb from `node` to the nearest enclosing call expression, in any of the
+/// three recognized forms.
+pub fn enclosing_call(node: Node) -> Option<Node> {
+    let mut current = node;
+    while !is_call_expression(current.kind()) {
+        current = current.parent()?;
+    }
+    Some(current)
+}
+
+/// The first direct child of the tree root with the given kind.
+pub fn find_root_child<'a>(tree: &'a Tree, kind: &str) -> Option<Node<'a>> {
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    if cursor.goto_first_child() {
+        loop {
+            let child = cursor.node();
+            if child.kind() == kind {
+                return Some(child);
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// Read the first few lines of a template file for a hover preview.
+pub fn template_preview(path: &Path) -> Option<String> {
+    const PREVIEW_LINES: usize = 10;
+
+    let contents = std::fs::read_to_string(path).ok()?;
+    let preview = contents
+        .lines()
+        .take(PREVIEW_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (!preview.trim().is_empty()).then_some(preview)
+}
+
+/// Convert a `file://` document URI into a filesystem path for path folding.
+///
+/// URIs arrive percent-encoded, so the remainder after the `file://` prefix
+/// is percent-decoded before being treated as a path.
+pub fn document_path_from_uri(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    Some(PathBuf::from(percent_decode_path(encoded)))
+}
+
+/// Percent-decode a URI component, e.g. `%20` back into a space.
+fn percent_decode_path(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|pair| std::str::from_utf8(pair).ok())
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok());
+            if let Some(byte) = hex {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Whether `name` (without the leading `$`) is a PHP superglobal, which is
+/// always in scope and so never a template-data key.
+pub fn is_superglobal(name: &str) -> bool {
+    matches!(
+        name,
+        "GLOBALS"
+            | "_SERVER"
+            | "_GET"
+            | "_POST"
+            | "_FILES"
+            | "_COOKIE"
+            | "_SESSION"
+            | "_REQUEST"
+            | "_ENV"
+    )
+}
+
+/// Whether a node is a plain PHP literal (no runtime evaluation).
+pub fn is_literal(node: &Node) -> bool {
+    if node.kind() == "encapsed_string" {
+        // A double-quoted string is only literal when it interpolates nothing:
+        // plain content and escape sequences are leaf tokens, while an
+        // interpolated variable or expression has children of its own.
+        let mut cursor = node.walk();
+        let is_plain = node
+            .named_children(&mut cursor)
+            .all(|child| child.child_count() == 0);
+        return is_plain;
+    }
+    matches!(
+        node.kind(),
+        "string" | "integer" | "float" | "boolean" | "true" | "false" | "null"
+    )
+}
+
+/// Strip a single pair of matching surrounding quotes from a string literal.
+pub fn strip_quotes(value: &str) -> &str {
+    let trimmed = value.trim();
+    let bytes = trimmed.as_bytes();
+    if trimmed.len() >= 2
+        && (bytes[0] == b'\'' || bytes[0] == b'"')
+        && bytes[trimmed.len() - 1] == bytes[0]
+    {
+        &trimmed[1..trimmed.len() - 1]
+    } else {
+        trimmed
+    }
+}
+
+/// Fold `.` and `..` segments of a path without touching the filesystem.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
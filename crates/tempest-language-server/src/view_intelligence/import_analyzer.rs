@@ -1,17 +1,37 @@
 use crate::view_intelligence::ast_traversal::AstTraversal;
-use crate::view_intelligence::types::{ImportInfo, Result, ViewAnalysisError, ViewImportType};
+use crate::view_intelligence::query::ViewQuery;
+use crate::view_intelligence::types::{Result, ViewAnalysisError, ViewImportType};
+use lsp_types::Range;
 use std::collections::HashMap;
 use tree_sitter::{Node, Tree};
 
+#[derive(Debug, Clone)]
+struct ImportInfo {
+    namespace: String,
+    function_name: String,
+    alias: Option<String>,
+    /// Range of the whole `use` declaration this import came from.
+    range: Range,
+}
+
 pub struct ImportAnalyzer;
 
 impl ImportAnalyzer {
     pub fn analyze_imports(tree: &Tree, text: &str) -> Result<HashMap<String, ViewImportType>> {
+        Self::analyze_imports_from(&ViewQuery::run(tree, text).use_declarations, text)
+    }
+
+    /// Build the import table from the `namespace_use_declaration` nodes already
+    /// captured by [`ViewQuery`].
+    pub fn analyze_imports_from(
+        use_declarations: &[Node],
+        text: &str,
+    ) -> Result<HashMap<String, ViewImportType>> {
         let mut imports = HashMap::new();
 
         Self::add_direct_namespace_imports(&mut imports);
 
-        let import_infos = Self::extract_import_statements(tree, text)?;
+        let import_infos = Self::extract_import_statements(use_declarations, text)?;
 
         for import_info in import_infos {
             if import_info.namespace == "Tempest" && import_info.function_name == "view" {
@@ -41,12 +61,14 @@ impl ImportAnalyzer {
         imports.insert("Tempest\\view".to_string(), ViewImportType::DirectNamespace);
     }
 
-    fn extract_import_statements(tree: &Tree, text: &str) -> Result<Vec<ImportInfo>> {
-        let import_nodes = AstTraversal::find_nodes_by_kind(tree, "namespace_use_declaration");
+    fn extract_import_statements(
+        import_nodes: &[Node],
+        text: &str,
+    ) -> Result<Vec<ImportInfo>> {
         let mut import_infos = Vec::new();
 
         for node in import_nodes {
-            if let Ok(import_info) = Self::parse_use_declaration_ast(&node, text) {
+            if let Ok(import_info) = Self::parse_use_declaration_ast(node, text) {
                 import_infos.push(import_info);
             }
         }
@@ -54,6 +76,48 @@ impl ImportAnalyzer {
         Ok(import_infos)
     }
 
+    /// Locate the Tempest `view` function imports in a document, returning the
+    /// local name / import type / declaration range for each, alongside any
+    /// malformed function-import declarations that should be reported.
+    #[allow(clippy::type_complexity)]
+    pub fn located_view_imports(
+        use_nodes: &[Node],
+        text: &str,
+    ) -> (Vec<(String, ViewImportType, Range)>, Vec<(String, Range)>) {
+        let mut imports = Vec::new();
+        let mut malformed = Vec::new();
+
+        for node in use_nodes {
+            // Only scrutinize `use function ...;` declarations; class imports are
+            // none of our concern.
+            if !Self::is_function_use_declaration(node, text).unwrap_or(false) {
+                continue;
+            }
+
+            match Self::parse_use_declaration_ast(node, text) {
+                Ok(info) if info.namespace == "Tempest" && info.function_name == "view" => {
+                    let (name, import_type) = match info.alias {
+                        Some(alias) => {
+                            (alias.clone(), ViewImportType::FunctionImportWithAlias(alias))
+                        }
+                        None => ("view".to_string(), ViewImportType::FunctionImport),
+                    };
+                    imports.push((name, import_type, info.range));
+                }
+                Ok(_) => {}
+                // A grouped import that simply doesn't mention `view` is fine.
+                Err(ViewAnalysisError::InvalidImportFormat(message))
+                    if !message.contains("not found") =>
+                {
+                    malformed.push((message, AstTraversal::node_range(node)));
+                }
+                Err(_) => {}
+            }
+        }
+
+        (imports, malformed)
+    }
+
     fn parse_use_declaration_ast(node: &Node, text: &str) -> Result<ImportInfo> {
         let is_function_import = Self::is_function_use_declaration(node, text)?;
 
@@ -63,10 +127,12 @@ impl ImportAnalyzer {
             ));
         }
 
-        Self::extract_use_clause_info(node, text)
+        let mut info = Self::extract_use_clause_info(node, text)?;
+        info.range = AstTraversal::node_range(node);
+        Ok(info)
     }
 
-    fn is_function_use_declaration(node: &Node, text: &str) -> Result<bool> {
+    pub fn is_function_use_declaration(node: &Node, text: &str) -> Result<bool> {
         if AstTraversal::find_child_by_kind(node, "function").is_some() {
             return Ok(true);
         }
@@ -129,6 +195,7 @@ impl ImportAnalyzer {
             namespace,
             function_name,
             alias,
+            range: Range::default(),
         })
     }
 
@@ -172,10 +239,11 @@ impl ImportAnalyzer {
             namespace,
             function_name: "view".to_string(),
             alias: None,
+            range: Range::default(),
         })
     }
 
-    fn extract_qualified_name_parts(node: &Node, text: &str) -> Result<Vec<String>> {
+    pub fn extract_qualified_name_parts(node: &Node, text: &str) -> Result<Vec<String>> {
         let mut parts = Vec::new();
 
         AstTraversal::traverse_children(node, |child| match child.kind() {
@@ -1,33 +1,639 @@
-use lsp_types::MessageType;
-use tower_lsp_server::Client;
-use tree_sitter::Tree;
-
-// Module declarations
 mod ast_traversal;
 mod call_analyzer;
 mod formatter;
+mod helpers;
 mod import_analyzer;
+mod path_resolver;
+mod query;
+mod symbols;
+mod template_analyzer;
+mod template_context;
 mod types;
+mod view_data;
+mod view_diagnostics;
+
+pub use types::{
+    RendererRegistry, ResolvedPath, ViewAnalysisResult, ViewCodeAction, ViewImportType,
+    ViewParameter, ViewRenderLens,
+};
 
-// Re-export main public types (others available via full path if needed)
+use crate::template_index::{ProvidedData, TemplateIndex};
+use ast_traversal::AstTraversal;
 use call_analyzer::FunctionCallAnalyzer;
 use formatter::ViewAnalysisFormatter;
+use helpers::{
+    document_path_from_uri, enclosing_call, end_position, find_root_child, is_call_expression,
+    path_argument_node, position_at, position_within, template_label, template_preview,
+    zero_width,
+};
 use import_analyzer::ImportAnalyzer;
-pub use types::ViewAnalysisResult;
-
-// Tests module
-#[cfg(test)]
-mod tests;
+use lsp_types::{
+    Diagnostic, DocumentSymbol, MessageType, Position, Range, SymbolKind, TextEdit,
+};
+use path_resolver::TemplatePathResolver;
+use query::{CallKind, CapturedCall, ViewQuery};
+use std::path::{Path, PathBuf};
+use symbols::{enclosing_declaration, SymbolTree};
+use template_analyzer::TemplateAnalyzer;
+use tower_lsp_server::Client;
+use tree_sitter::{Node, Tree};
+use view_data::ViewData;
+use view_diagnostics::ViewDiagnostics;
 
-/// Main API for view intelligence analysis
 pub struct ViewIntelligence;
 
 impl ViewIntelligence {
-    /// Analyze a document for Tempest view function usage
-    pub async fn analyze_document(client: &Client, tree: &Tree, text: &str, uri: &str) {
+    /// Resolve a go-to-definition request at `offset` to the template file a
+    /// `view()` call renders.
+    pub fn resolve_definition(
+        tree: &Tree,
+        text: &str,
+        offset: usize,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+    ) -> Option<PathBuf> {
+        let node = tree.root_node().descendant_for_byte_range(offset, offset)?;
+
+        let call = enclosing_call(node)?;
+        let captured = CapturedCall::from_node(call);
+
+        let imports =
+            ImportAnalyzer::analyze_imports(tree, text).unwrap_or_default();
+        registry.recognize(&captured, &imports, text)?;
+
+        let argument = path_argument_node(captured.arguments)?;
+        TemplatePathResolver::resolve_node(&argument, text, document_path, workspace_root)
+    }
+
+    /// Build a Markdown hover for the recognized `view()` call at `offset`.
+    ///
+    /// Summarizes the resolved template path, the import style, and the data the
+    /// template receives, and previews the template's opening lines when it
+    /// exists on disk.
+    pub fn hover(
+        tree: &Tree,
+        text: &str,
+        offset: usize,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+    ) -> Option<String> {
+        let node = tree.root_node().descendant_for_byte_range(offset, offset)?;
+
+        let call = enclosing_call(node)?;
+        let captured = CapturedCall::from_node(call);
+
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        registry.recognize(&captured, &imports, text)?;
+
+        let view_call =
+            FunctionCallAnalyzer::extract_view_call_info(&captured, text, Some(document_path)).ok()?;
+
+        // Describe how the renderer is reached; free functions additionally
+        // report their import style.
+        let style = match captured.kind {
+            CallKind::Function => imports
+                .get(&view_call.function_name)
+                .map(ViewImportType::description)
+                .unwrap_or("function call"),
+            CallKind::Method => "method call",
+            CallKind::Static => "static call",
+        };
+
+        let mut markdown = format!("**Tempest `view()`** — {style}\n");
+
+        if let Some(argument) = path_argument_node(call.child_by_field_name("arguments")) {
+            if let Some(candidate) = TemplatePathResolver::resolve_candidate(
+                &argument,
+                text,
+                document_path,
+                workspace_root,
+            ) {
+                markdown.push_str(&format!("\nTemplate: `{}`\n", candidate.display()));
+
+                if candidate.is_file() {
+                    if let Some(preview) = template_preview(&candidate) {
+                        markdown.push_str(&format!("\n```php\n{preview}\n```\n"));
+                    }
+                } else {
+                    markdown.push_str("\n⚠️ Template file not found.\n");
+                }
+            }
+        }
+
+        // Data the template receives: every argument other than the path. The
+        // original index in `parameters` is kept so a positional value is
+        // labeled by its real argument position, not its position among the
+        // data rows.
+        let data: Vec<(usize, &ViewParameter)> = view_call
+            .parameters
+            .iter()
+            .enumerate()
+            .filter(|(index, param)| {
+                !(*index == 0 && param.name.is_none())
+                    && param.name.as_deref() != Some("path")
+            })
+            .collect();
+
+        if !data.is_empty() {
+            markdown.push_str("\n| Key | Value |\n| --- | --- |\n");
+            for (index, param) in &data {
+                let key = match &param.name {
+                    Some(name) => name.clone(),
+                    None => format!("[{index}]"),
+                };
+                markdown.push_str(&format!("| {} | `{}` |\n", key, param.value));
+            }
+        }
+
+        Some(markdown)
+    }
+
+    /// Every recognized `view()` render in the document whose template path
+    /// resolves to a file on disk, as `(resolved target, path-argument range,
+    /// provided data)`.
+    ///
+    /// This backs both the document-link provider and the template reverse
+    /// index; the latter uses the provided-data summary to aggregate view data
+    /// across call sites.
+    pub fn view_render_sites(
+        tree: &Tree,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+    ) -> Vec<(PathBuf, Range, ProvidedData)> {
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        let mut sites = Vec::new();
+        for call in &query.calls {
+            if registry.recognize(call, &imports, text).is_none() {
+                continue;
+            }
+            let Some(argument) = path_argument_node(call.arguments) else {
+                continue;
+            };
+            if let Some(target) =
+                TemplatePathResolver::resolve_node(&argument, text, document_path, workspace_root)
+            {
+                let data = ViewData::provided(call.arguments, text).into_provided();
+                sites.push((target, AstTraversal::node_range(&argument), data));
+            }
+        }
+
+        sites
+    }
+
+    /// Emit a document link for every recognized `view()` call whose template
+    /// path resolves to a file on disk, spanning the path argument.
+    pub fn document_links(
+        tree: &Tree,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+    ) -> Vec<(Range, PathBuf)> {
+        Self::view_render_sites(tree, text, document_path, workspace_root, registry)
+            .into_iter()
+            .map(|(target, range, _)| (range, target))
+            .collect()
+    }
+
+    /// The templates embedded by the `.view.php` file at `document_path`, as
+    /// `(reference range, resolved target)` pairs.
+    ///
+    /// Nested references come from PHP renders (`view(...)`, `include`) and
+    /// `<x-component>` tags. The resolved targets seed the template dependency
+    /// graph; the ranges back partial go-to-definition.
+    pub fn template_dependencies(
+        tree: &Tree,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Vec<(Range, PathBuf)> {
+        TemplateAnalyzer::dependencies(tree, text, document_path, workspace_root)
+            .into_iter()
+            .map(|dependency| (dependency.range, dependency.target))
+            .collect()
+    }
+
+    /// Resolve a go-to-definition request at `offset` to the partial referenced
+    /// under the cursor inside a `.view.php` template.
+    ///
+    /// This complements [`Self::resolve_definition`], which only covers the PHP
+    /// `view()` call form: here an `<x-component>` tag resolves to its defining
+    /// template file.
+    pub fn resolve_template_dependency(
+        tree: &Tree,
+        text: &str,
+        offset: usize,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let position = position_at(text, offset);
+        TemplateAnalyzer::dependencies(tree, text, document_path, workspace_root)
+            .into_iter()
+            .find(|dependency| position_within(position, dependency.range))
+            .map(|dependency| dependency.target)
+    }
+
+    /// Every recognized `view()` render in the document, surfaced as a document
+    /// symbol nested under its enclosing class and method.
+    ///
+    /// Each render is labeled with the statically resolved template path, or the
+    /// call text when the expression is too dynamic to evaluate.
+    pub fn document_symbols(
+        tree: &Tree,
+        text: &str,
+        document_path: &Path,
+        registry: &RendererRegistry,
+    ) -> Vec<DocumentSymbol> {
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        let mut tree_builder = SymbolTree::default();
+        for call in &query.calls {
+            if registry.recognize(call, &imports, text).is_none() {
+                continue;
+            }
+
+            let label = match Self::resolved_path(call, text, document_path) {
+                ResolvedPath::Resolved(path) => template_label(&path),
+                ResolvedPath::Unresolvable { .. } => AstTraversal::extract_node_text(&call.node, text)
+                    .unwrap_or_else(|_| "view(…)".to_string()),
+            };
+
+            #[allow(deprecated)]
+            let render = DocumentSymbol {
+                name: label,
+                detail: call
+                    .function
+                    .and_then(|node| AstTraversal::extract_node_text(&node, text).ok()),
+                kind: SymbolKind::OBJECT,
+                tags: None,
+                deprecated: None,
+                range: AstTraversal::node_range(&call.node),
+                selection_range: call
+                    .function
+                    .map(|node| AstTraversal::node_range(&node))
+                    .unwrap_or_else(|| AstTraversal::node_range(&call.node)),
+                children: None,
+            };
+
+            let class = enclosing_declaration(
+                call.node,
+                &["class_declaration", "enum_declaration", "trait_declaration"],
+                text,
+            );
+            let method = enclosing_declaration(
+                call.node,
+                &["method_declaration", "function_definition"],
+                text,
+            );
+            tree_builder.insert(class, method, render);
+        }
+
+        tree_builder.finish()
+    }
+
+    /// Per-render code-lens anchors: the range to render the lens above and the
+    /// resolved target, or `None` when the path expression is dynamic.
+    pub fn view_render_lenses(
+        tree: &Tree,
+        text: &str,
+        document_path: &Path,
+        registry: &RendererRegistry,
+    ) -> Vec<ViewRenderLens> {
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        let mut lenses = Vec::new();
+        for call in &query.calls {
+            if registry.recognize(call, &imports, text).is_none() {
+                continue;
+            }
+            let target = match Self::resolved_path(call, text, document_path) {
+                ResolvedPath::Resolved(path) => Some(path),
+                ResolvedPath::Unresolvable { .. } => None,
+            };
+            lenses.push(ViewRenderLens {
+                range: AstTraversal::node_range(&call.node),
+                target,
+            });
+        }
+
+        lenses
+    }
+
+    /// Statically evaluate a recognized call's template-path argument.
+    fn resolved_path(call: &CapturedCall, text: &str, document_path: &Path) -> ResolvedPath {
+        path_argument_node(call.arguments)
+            .map(|argument| TemplatePathResolver::evaluate(&argument, text, Some(document_path)))
+            .unwrap_or(ResolvedPath::Unresolvable { prefix: None })
+    }
+
+    /// Code actions available for the `view()` call overlapping `range`.
+    ///
+    /// A fully-qualified `\Tempest\view(...)` / `Tempest\view(...)` call can be
+    /// rewritten to the bare `view(...)` form with a `use function Tempest\view;`
+    /// import added; the inverse qualifies a bare `view(...)` call.
+    pub fn code_actions(tree: &Tree, text: &str, range: Range) -> Vec<ViewCodeAction> {
+        let query = ViewQuery::run(tree, text);
+
+        let Some(call) = Self::call_at(&query.calls, range.start) else {
+            return Vec::new();
+        };
+        // Import normalization only applies to free-function `view()` calls.
+        if call.kind != CallKind::Function {
+            return Vec::new();
+        }
+        let Some(function_node) = call.function else {
+            return Vec::new();
+        };
+        let Ok(function_name) = AstTraversal::extract_node_text(&function_node, text) else {
+            return Vec::new();
+        };
+        let name_range = AstTraversal::node_range(&function_node);
+
+        match function_name.as_str() {
+            "Tempest\\view" | "\\Tempest\\view" => {
+                let mut edits = vec![TextEdit {
+                    range: name_range,
+                    new_text: "view".to_string(),
+                }];
+                if let Some(import_edit) =
+                    Self::import_insertion_edit(tree, &query.use_declarations, text)
+                {
+                    edits.push(import_edit);
+                }
+                vec![ViewCodeAction {
+                    title: "Import `Tempest\\view` and call `view()`".to_string(),
+                    edits,
+                }]
+            }
+            "view" => vec![ViewCodeAction {
+                title: "Qualify as `\\Tempest\\view()`".to_string(),
+                edits: vec![TextEdit {
+                    range: name_range,
+                    new_text: "\\Tempest\\view".to_string(),
+                }],
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// The call node whose function name covers `position`.
+    fn call_at<'a, 'tree>(
+        calls: &'a [CapturedCall<'tree>],
+        position: Position,
+    ) -> Option<&'a CapturedCall<'tree>> {
+        calls.iter().find(|call| {
+            call.function
+                .map(|function| position_within(position, AstTraversal::node_range(&function)))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Build the edit that adds `Tempest\view` to the document's imports,
+    /// merging into an existing grouped `use function Tempest\{...}` when present
+    /// and otherwise adding a fresh `use function Tempest\view;` statement.
+    fn import_insertion_edit(tree: &Tree, use_nodes: &[Node], text: &str) -> Option<TextEdit> {
+        // Merge into an existing grouped Tempest function import.
+        for node in use_nodes {
+            if !ImportAnalyzer::is_function_use_declaration(node, text).unwrap_or(false) {
+                continue;
+            }
+            let Ok(node_text) = AstTraversal::extract_node_text(node, text) else {
+                continue;
+            };
+            if let Some(group) = AstTraversal::find_child_by_kind(node, "namespace_use_group") {
+                let Some(prefix) = AstTraversal::find_child_by_kind(node, "qualified_name") else {
+                    continue;
+                };
+                let Ok(prefix_parts) = ImportAnalyzer::extract_qualified_name_parts(&prefix, text)
+                else {
+                    continue;
+                };
+                if prefix_parts.join("\\") != "Tempest" {
+                    continue;
+                }
+
+                let mut names = Vec::new();
+                AstTraversal::traverse_children(&group, |clause| {
+                    if clause.kind() == "namespace_use_clause" {
+                        AstTraversal::traverse_children(clause, |part| {
+                            if part.kind() == "name" {
+                                if let Ok(name) = AstTraversal::extract_node_text(part, text) {
+                                    names.push(name);
+                                }
+                            }
+                        });
+                    }
+                });
+
+                if names.iter().any(|name| name == "view") {
+                    return None;
+                }
+                names.push("view".to_string());
+                names.sort();
+
+                return Some(TextEdit {
+                    range: AstTraversal::node_range(node),
+                    new_text: format!("use function Tempest\\{{{}}};", names.join(", ")),
+                });
+            }
+
+            // A plain `use function Tempest\view;` already covers us.
+            if node_text.contains("use function Tempest\\view") {
+                return None;
+            }
+        }
+
+        // Otherwise add a fresh statement after the last `use`, or after the
+        // namespace declaration, or at the top of the file.
+        if let Some(anchor) = use_nodes.iter().max_by_key(|node| node.end_byte()) {
+            return Some(TextEdit {
+                range: zero_width(end_position(anchor)),
+                new_text: "\nuse function Tempest\\view;".to_string(),
+            });
+        }
+
+        if let Some(namespace) = find_root_child(tree, "namespace_definition") {
+            return Some(TextEdit {
+                range: zero_width(end_position(&namespace)),
+                new_text: "\n\nuse function Tempest\\view;".to_string(),
+            });
+        }
+
+        Some(TextEdit {
+            range: zero_width(Position::new(1, 0)),
+            new_text: "use function Tempest\\view;\n".to_string(),
+        })
+    }
+
+    /// Compute the diagnostics to publish for a document.
+    ///
+    /// `templates` is the workspace reverse index, consulted so the undefined
+    /// view-variable check can aggregate the data provided across every call
+    /// site rendering a template rather than judging each call in isolation. It
+    /// must already reflect this document's current render sites.
+    pub fn diagnostics(
+        tree: &Tree,
+        text: &str,
+        document_path: Option<&Path>,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+        templates: &TemplateIndex,
+    ) -> Vec<Diagnostic> {
+        ViewDiagnostics::collect(tree, text, document_path, workspace_root, registry, templates)
+    }
+
+    /// Whether the cursor node sits inside the template-path string argument of
+    /// a `view()` call, making path completion appropriate.
+    pub fn is_in_view_path_argument(node: Node, text: &str) -> bool {
+        Self::view_path_argument(node, text).is_some()
+    }
+
+    /// Completions for the template path being typed at `offset`.
+    ///
+    /// Fires only inside the path argument of a recognized `view()` call (one
+    /// whose function is a known import). The directory typed so far is resolved
+    /// through the concatenation-aware path resolver and its `*.view.php` files
+    /// and sub-directories are offered. Directory entries carry a trailing `/`.
+    pub fn path_completions(
+        tree: &Tree,
+        text: &str,
+        offset: usize,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+    ) -> Vec<String> {
+        let Some(node) = tree.root_node().descendant_for_byte_range(offset, offset) else {
+            return Vec::new();
+        };
+        if Self::view_path_argument(node, text).is_none() {
+            return Vec::new();
+        }
+
+        let Some(call) = enclosing_call(node) else {
+            return Vec::new();
+        };
+        let captured = CapturedCall::from_node(call);
+
+        // Only offer completions for recognized renderers, mirroring how
+        // `analyze_document` filters calls.
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+        if registry.recognize(&captured, &imports, text).is_none() {
+            return Vec::new();
+        }
+
+        let Some(argument) = path_argument_node(captured.arguments) else {
+            return Vec::new();
+        };
+        let Some((directory, partial)) =
+            TemplatePathResolver::resolve_prefix(&argument, text, document_path, workspace_root, offset)
+        else {
+            return Vec::new();
+        };
+
+        Self::directory_entries(&directory, &partial)
+    }
+
+    /// Template files and sub-directories within `directory` whose name starts
+    /// with `partial`. Sub-directories are suffixed with `/`.
+    fn directory_entries(directory: &Path, partial: &str) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return Vec::new();
+        };
+
+        let mut completions = Vec::new();
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if !name.starts_with(partial) {
+                continue;
+            }
+
+            if entry.path().is_dir() {
+                completions.push(format!("{name}/"));
+            } else if name.ends_with(".view.php") {
+                completions.push(name);
+            }
+        }
+
+        completions.sort();
+        completions
+    }
+
+    /// Locate the `string`/`encapsed_string` ancestor of `node` that is the
+    /// first positional argument of a recognized `view()` call.
+    fn view_path_argument<'a>(node: Node<'a>, text: &str) -> Option<Node<'a>> {
+        // Climb to the nearest string literal.
+        let mut string_node = node;
+        while !matches!(string_node.kind(), "string" | "encapsed_string") {
+            string_node = string_node.parent()?;
+        }
+
+        // The string must be (inside) the first argument of the call.
+        let mut argument = string_node;
+        while argument.kind() != "argument" {
+            argument = argument.parent()?;
+        }
+        let arguments = argument.parent()?;
+        if arguments.kind() != "arguments" {
+            return None;
+        }
+        let first_argument =
+            AstTraversal::find_child_by_kind(&arguments, "argument").unwrap_or(argument);
+        if first_argument.id() != argument.id() {
+            return None;
+        }
+
+        let call = arguments.parent()?;
+        if !is_call_expression(call.kind()) {
+            return None;
+        }
+        // For free functions the callable is the `function` field; for method
+        // and static calls it is the `name` field.
+        let name_field = if call.kind() == "function_call_expression" {
+            "function"
+        } else {
+            "name"
+        };
+        let function_node = call.child_by_field_name(name_field)?;
+        let function_name = AstTraversal::extract_node_text(&function_node, text).ok()?;
+        if ViewDiagnostics::looks_like_view_call(&function_name) {
+            Some(string_node)
+        } else {
+            None
+        }
+    }
+
+    pub async fn analyze_document(
+        client: &Client,
+        tree: &Tree,
+        text: &str,
+        uri: &str,
+        registry: &RendererRegistry,
+    ) {
         let mut result = ViewAnalysisResult::new();
 
-        match ImportAnalyzer::analyze_imports(tree, text) {
+        // Walk the tree a single time, then dispatch the captured nodes to the
+        // import and call analyzers.
+        let query = ViewQuery::run(tree, text);
+
+        match ImportAnalyzer::analyze_imports_from(&query.use_declarations, text) {
             Ok(imports) => result.imports = imports,
             Err(e) => {
                 client
@@ -40,24 +646,35 @@ impl ViewIntelligence {
             }
         }
 
-        let all_calls = match FunctionCallAnalyzer::find_function_calls(tree, text) {
-            Ok(calls) => calls,
-            Err(e) => {
-                client
-                    .log_message(
-                        MessageType::ERROR,
-                        format!("Function call analysis failed for {uri}: {e}"),
-                    )
-                    .await;
-                return;
-            }
-        };
+        let document_path = document_path_from_uri(uri);
 
-        result.calls = all_calls
-            .into_iter()
-            .filter(|call| result.imports.contains_key(&call.function_name))
+        // Keep only the calls that resolve to a recognized renderer: imported
+        // `view()` free functions, or the configured method/static selectors.
+        let recognized: Vec<&CapturedCall> = query
+            .calls
+            .iter()
+            .filter(|call| registry.recognize(call, &result.imports, text).is_some())
             .collect();
 
+        for call in recognized {
+            match FunctionCallAnalyzer::extract_view_call_info(call, text, document_path.as_deref())
+            {
+                Ok(view_call) => result.calls.push(view_call),
+                Err(e) => {
+                    client
+                        .log_message(
+                            MessageType::ERROR,
+                            format!("Function call analysis failed for {uri}: {e}"),
+                        )
+                        .await;
+                    return;
+                }
+            }
+        }
+
         ViewAnalysisFormatter::log_analysis_results(client, &result, uri).await;
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,349 @@
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use crate::view_intelligence::helpers::{normalize_path, strip_quotes};
+use crate::view_intelligence::types::ResolvedPath;
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+/// Resolves the template-path argument of a `view()` call to an on-disk file.
+///
+/// Tempest accepts two broad styles of path argument: a PHP path expression
+/// anchored at `__DIR__` (`__DIR__ . '/../Views/home.view.php'`) and a
+/// dotted/relative view name resolved against the project root (`pages.home`).
+pub struct TemplatePathResolver;
+
+impl TemplatePathResolver {
+    /// Resolve a path-argument node to an existing template file.
+    ///
+    /// The argument is typically a PHP string concatenation such as
+    /// `__DIR__ . '/../Views/home.view.php'`, so we walk its sub-tree in order:
+    /// `__DIR__`/`__FILE__` are substituted with the current document's
+    /// directory/path and string literals contribute their unquoted contents.
+    /// Any other operand (a variable, a method call) makes the expression
+    /// unresolvable and yields `None`. The pieces are joined and `..` segments
+    /// normalized against the resulting base.
+    pub fn resolve_node(
+        argument: &Node,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let normalized = Self::resolve_candidate(argument, text, document_path, workspace_root)?;
+        normalized.is_file().then_some(normalized)
+    }
+
+    /// Like [`Self::resolve_node`] but returns the normalized candidate path
+    /// even when it does not exist on disk; `None` only when the expression is
+    /// too dynamic to resolve statically. Used to diagnose missing templates.
+    ///
+    /// Folding goes through [`Self::fold`], so a non-constant operand (`$var`, a
+    /// method call, the non-`.` use of an operator) marks the whole expression
+    /// dynamic and yields `None` rather than a guess built from the constant
+    /// fragments alone.
+    pub fn resolve_candidate(
+        argument: &Node,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let mut parts = Vec::new();
+        let mut anchored = false;
+        if Self::fold(argument, text, Some(document_path), &mut parts, &mut anchored) {
+            return None;
+        }
+
+        let joined = parts.concat();
+        if joined.is_empty() {
+            return None;
+        }
+
+        let candidate = if anchored {
+            PathBuf::from(joined)
+        } else if joined.contains('/') || joined.ends_with(".view.php") {
+            Self::base(document_path, workspace_root).join(&joined)
+        } else {
+            // Dotted view name, e.g. `pages.home` -> `pages/home.view.php`.
+            Self::base(document_path, workspace_root)
+                .join(format!("{}.view.php", joined.replace('.', "/")))
+        };
+
+        Some(normalize_path(&candidate))
+    }
+
+    /// Resolve the directory and partial filename typed so far inside a path
+    /// argument, up to `cursor`.
+    ///
+    /// Used to drive completion: `__DIR__ . '/../Views/ho` resolves to the
+    /// normalized `Views` directory with the partial `ho`.
+    pub fn resolve_prefix(
+        argument: &Node,
+        text: &str,
+        document_path: &Path,
+        workspace_root: Option<&Path>,
+        cursor: usize,
+    ) -> Option<(PathBuf, String)> {
+        let mut parts = Vec::new();
+        let mut anchored = false;
+        Self::collect_until(argument, text, document_path, cursor, &mut parts, &mut anchored);
+
+        let joined = parts.concat();
+        if joined.is_empty() && !anchored {
+            return None;
+        }
+
+        let (dir_part, partial) = match joined.rfind('/') {
+            Some(index) => (joined[..=index].to_string(), joined[index + 1..].to_string()),
+            None => (String::new(), joined.clone()),
+        };
+
+        let directory = if anchored {
+            PathBuf::from(if dir_part.is_empty() { "/" } else { &dir_part })
+        } else if dir_part.is_empty() {
+            Self::base(document_path, workspace_root)
+        } else {
+            Self::base(document_path, workspace_root).join(dir_part.trim_start_matches('/'))
+        };
+
+        Some((normalize_path(&directory), partial))
+    }
+
+    /// Walk an expression sub-tree for completion, appending each constant
+    /// operand's contribution in source order but only considering operands at
+    /// or before `cursor`, and truncating the literal that contains the cursor
+    /// to its typed prefix.
+    fn collect_until(
+        node: &Node,
+        text: &str,
+        document_path: &Path,
+        cursor: usize,
+        parts: &mut Vec<String>,
+        anchored: &mut bool,
+    ) {
+        if node.start_byte() > cursor {
+            return;
+        }
+
+        match node.kind() {
+            "string" | "encapsed_string" => {
+                if cursor >= node.end_byte() {
+                    if let Ok(raw) = AstTraversal::extract_node_text(node, text) {
+                        parts.push(strip_quotes(&raw).to_string());
+                    }
+                } else if let Some(inner) = text.get(node.start_byte() + 1..cursor) {
+                    parts.push(inner.to_string());
+                }
+            }
+            "name" => {
+                if let Ok(token) = AstTraversal::extract_node_text(node, text) {
+                    match token.as_str() {
+                        "__DIR__" => {
+                            if let Some(dir) = document_path.parent() {
+                                parts.push(dir.to_string_lossy().into_owned());
+                                *anchored = true;
+                            }
+                        }
+                        "__FILE__" => {
+                            parts.push(document_path.to_string_lossy().into_owned());
+                            *anchored = true;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => AstTraversal::traverse_children(node, |child| {
+                Self::collect_until(&child, text, document_path, cursor, parts, anchored);
+            }),
+        }
+    }
+
+    fn base(document_path: &Path, workspace_root: Option<&Path>) -> PathBuf {
+        workspace_root
+            .map(Path::to_path_buf)
+            .or_else(|| document_path.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
+
+    /// Statically evaluate a view-path argument expression into a [`ResolvedPath`].
+    ///
+    /// Folds string literals, `__DIR__`/`__FILE__`, `dirname(__DIR__, n)` and
+    /// `.` concatenation in source order. The first non-constant operand stops
+    /// the fold: the expression is reported [`ResolvedPath::Unresolvable`] while
+    /// still carrying the constant prefix gathered so far.
+    pub fn evaluate(argument: &Node, text: &str, document_path: Option<&Path>) -> ResolvedPath {
+        let mut parts = Vec::new();
+        let mut anchored = false;
+        let dynamic = Self::fold(argument, text, document_path, &mut parts, &mut anchored);
+
+        let prefix = Self::materialize(&parts.concat(), anchored, document_path);
+
+        if dynamic {
+            ResolvedPath::Unresolvable { prefix }
+        } else {
+            match prefix {
+                Some(path) => ResolvedPath::Resolved(path),
+                None => ResolvedPath::Unresolvable { prefix: None },
+            }
+        }
+    }
+
+    /// Fold one expression node, appending its constant contribution to `parts`.
+    /// Returns `true` once a non-constant operand is encountered, after which no
+    /// further operands are folded.
+    fn fold(
+        node: &Node,
+        text: &str,
+        document_path: Option<&Path>,
+        parts: &mut Vec<String>,
+        anchored: &mut bool,
+    ) -> bool {
+        match node.kind() {
+            "string" | "encapsed_string" => {
+                if let Ok(raw) = AstTraversal::extract_node_text(node, text) {
+                    parts.push(strip_quotes(&raw).to_string());
+                }
+                false
+            }
+            "name" => match AstTraversal::extract_node_text(node, text).as_deref() {
+                Ok("__DIR__") => {
+                    match document_path.and_then(Path::parent) {
+                        Some(dir) => {
+                            parts.push(dir.to_string_lossy().into_owned());
+                            *anchored = true;
+                            false
+                        }
+                        None => true,
+                    }
+                }
+                Ok("__FILE__") => match document_path {
+                    Some(path) => {
+                        parts.push(path.to_string_lossy().into_owned());
+                        *anchored = true;
+                        false
+                    }
+                    None => true,
+                },
+                _ => true,
+            },
+            "binary_expression" => {
+                // Only string concatenation contributes; any other operator is
+                // not a path expression.
+                if node
+                    .child_by_field_name("operator")
+                    .and_then(|op| AstTraversal::extract_node_text(&op, text).ok())
+                    .as_deref()
+                    != Some(".")
+                {
+                    return true;
+                }
+                let Some(left) = node.child_by_field_name("left") else {
+                    return true;
+                };
+                if Self::fold(&left, text, document_path, parts, anchored) {
+                    return true;
+                }
+                match node.child_by_field_name("right") {
+                    Some(right) => Self::fold(&right, text, document_path, parts, anchored),
+                    None => true,
+                }
+            }
+            "function_call_expression" => {
+                let is_dirname = node
+                    .child_by_field_name("function")
+                    .and_then(|f| AstTraversal::extract_node_text(&f, text).ok())
+                    .as_deref()
+                    == Some("dirname");
+                match is_dirname
+                    .then(|| Self::eval_dirname(node, text, document_path))
+                    .flatten()
+                {
+                    Some(dir) => {
+                        parts.push(dir);
+                        *anchored = true;
+                        false
+                    }
+                    None => true,
+                }
+            }
+            // Unwrap the argument wrapper and parentheses, skipping the `name:`
+            // of a named argument so `view(path: '...')` still folds.
+            "argument" | "parenthesized_expression" => {
+                let named = node.child_by_field_name("name");
+                let mut dynamic = false;
+                let mut folded = false;
+                let mut cursor = node.walk();
+                for child in node.named_children(&mut cursor) {
+                    if Some(child) == named {
+                        continue;
+                    }
+                    folded = true;
+                    if Self::fold(&child, text, document_path, parts, anchored) {
+                        dynamic = true;
+                        break;
+                    }
+                }
+                dynamic || !folded
+            }
+            _ => true,
+        }
+    }
+
+    /// Evaluate `dirname(__DIR__ | __FILE__, n)` into its directory string,
+    /// returning `None` when the arguments aren't statically known.
+    fn eval_dirname(node: &Node, text: &str, document_path: Option<&Path>) -> Option<String> {
+        let arguments = node.child_by_field_name("arguments")?;
+        let args = AstTraversal::find_children_by_kind(&arguments, "argument");
+
+        let base = args.first()?;
+        let mut parts = Vec::new();
+        let mut anchored = false;
+        if Self::fold(base, text, document_path, &mut parts, &mut anchored) || !anchored {
+            return None;
+        }
+
+        let levels = args
+            .get(1)
+            .and_then(|node| Self::integer_value(node, text))
+            .unwrap_or(1);
+
+        let mut path = PathBuf::from(parts.concat());
+        for _ in 0..levels {
+            path = path.parent()?.to_path_buf();
+        }
+        Some(path.to_string_lossy().into_owned())
+    }
+
+    /// Parse a positive integer literal argument (e.g. the levels of `dirname`).
+    fn integer_value(node: &Node, text: &str) -> Option<u32> {
+        let mut result = None;
+        AstTraversal::traverse_children(node, |child| {
+            if result.is_none() && child.kind() == "integer" {
+                if let Ok(raw) = AstTraversal::extract_node_text(child, text) {
+                    result = raw.trim().parse().ok();
+                }
+            }
+        });
+        result
+    }
+
+    /// Turn the folded literal pieces into a normalized candidate path, applying
+    /// the same dotted-name rule as [`Self::resolve_candidate`]. Unanchored
+    /// paths are always resolved against the document's directory; the
+    /// workspace root is not consulted here.
+    fn materialize(joined: &str, anchored: bool, document_path: Option<&Path>) -> Option<PathBuf> {
+        if joined.is_empty() {
+            return None;
+        }
+
+        let candidate = if anchored {
+            PathBuf::from(joined)
+        } else {
+            let base = document_path?.parent()?.to_path_buf();
+            if joined.contains('/') || joined.ends_with(".view.php") {
+                base.join(joined)
+            } else {
+                base.join(format!("{}.view.php", joined.replace('.', "/")))
+            }
+        };
+
+        Some(normalize_path(&candidate))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use std::sync::OnceLock;
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Node, Query, QueryCursor, Tree};
+
+/// Source of the single compiled query used to drive view analysis.
+///
+/// Capturing both the `use` declarations and the function calls in one pattern
+/// lets us walk the tree a single time per analysis instead of once per node
+/// kind, which matters now that analysis runs on every keystroke.
+const VIEW_QUERY_SOURCE: &str = r#"
+(namespace_use_declaration) @use_decl
+(function_call_expression
+  function: (_) @call.function
+  arguments: (_)? @call.args) @call
+(member_call_expression
+  name: (_) @call.function
+  arguments: (_)? @call.args) @call
+(scoped_call_expression
+  name: (_) @call.function
+  arguments: (_)? @call.args) @call
+"#;
+
+/// How a recognized call expresses the renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    /// A free function, e.g. `view(...)`.
+    Function,
+    /// A method call, e.g. `$this->view(...)`.
+    Method,
+    /// A static call, e.g. `View::create(...)`.
+    Static,
+}
+
+impl CallKind {
+    pub fn from_node_kind(kind: &str) -> Self {
+        match kind {
+            "member_call_expression" => CallKind::Method,
+            "scoped_call_expression" => CallKind::Static,
+            _ => CallKind::Function,
+        }
+    }
+}
+
+/// A `view()` call and the sub-nodes captured alongside it in one query match,
+/// so later passes don't have to re-descend for the function name or arguments.
+pub struct CapturedCall<'tree> {
+    pub node: Node<'tree>,
+    pub kind: CallKind,
+    /// The callable-name node: the function name, method name, or static
+    /// method name depending on [`CapturedCall::kind`].
+    pub function: Option<Node<'tree>>,
+    pub arguments: Option<Node<'tree>>,
+}
+
+impl<'tree> CapturedCall<'tree> {
+    /// Wrap a call node reached outside the query pass (e.g. by climbing up from
+    /// the cursor), recovering the same sub-nodes the query would have captured.
+    pub fn from_node(node: Node<'tree>) -> Self {
+        Self {
+            kind: CallKind::from_node_kind(node.kind()),
+            function: node.child_by_field_name(if node.kind() == "function_call_expression" {
+                "function"
+            } else {
+                "name"
+            }),
+            arguments: node.child_by_field_name("arguments"),
+            node,
+        }
+    }
+
+    /// The normalized selector used to key this call against the renderer
+    /// registry: the plain name for functions and methods, `Class::method` for
+    /// static calls.
+    pub fn selector(&self, text: &str) -> Option<String> {
+        let name = AstTraversal::extract_node_text(&self.function?, text).ok()?;
+        match self.kind {
+            CallKind::Function | CallKind::Method => Some(name),
+            CallKind::Static => {
+                let scope = self.node.child_by_field_name("scope")?;
+                let scope = AstTraversal::extract_node_text(&scope, text).ok()?;
+                Some(format!("{scope}::{name}"))
+            }
+        }
+    }
+}
+
+/// Nodes of interest gathered from a document in a single query pass.
+pub struct ViewQuery<'tree> {
+    pub use_declarations: Vec<Node<'tree>>,
+    pub calls: Vec<CapturedCall<'tree>>,
+}
+
+impl<'tree> ViewQuery<'tree> {
+    /// Run the compiled view query over the whole tree once, dispatching each
+    /// match to the appropriate bucket.
+    pub fn run(tree: &'tree Tree, text: &str) -> Self {
+        let query = Self::compiled(tree);
+        let mut cursor = QueryCursor::new();
+
+        let use_index = query.capture_index_for_name("use_decl");
+        let call_index = query.capture_index_for_name("call");
+        let function_index = query.capture_index_for_name("call.function");
+        let args_index = query.capture_index_for_name("call.args");
+
+        let mut use_declarations = Vec::new();
+        let mut calls = Vec::new();
+
+        let mut matches = cursor.matches(query, tree.root_node(), text.as_bytes());
+        while let Some(matched) = matches.next() {
+            let mut call_node = None;
+            let mut function = None;
+            let mut arguments = None;
+
+            for capture in matched.captures {
+                let index = Some(capture.index);
+                if index == use_index {
+                    use_declarations.push(capture.node);
+                } else if index == call_index {
+                    call_node = Some(capture.node);
+                } else if index == function_index {
+                    function = Some(capture.node);
+                } else if index == args_index {
+                    arguments = Some(capture.node);
+                }
+            }
+
+            if let Some(node) = call_node {
+                calls.push(CapturedCall {
+                    kind: CallKind::from_node_kind(node.kind()),
+                    node,
+                    function,
+                    arguments,
+                });
+            }
+        }
+
+        Self {
+            use_declarations,
+            calls,
+        }
+    }
+
+    /// The query, compiled lazily once and reused for every document.
+    fn compiled(tree: &Tree) -> &'static Query {
+        static QUERY: OnceLock<Query> = OnceLock::new();
+        QUERY.get_or_init(|| {
+            Query::new(&tree.language(), VIEW_QUERY_SOURCE).expect("view query should compile")
+        })
+    }
+}
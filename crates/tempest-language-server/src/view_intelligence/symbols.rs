@@ -0,0 +1,132 @@
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use lsp_types::{DocumentSymbol, SymbolKind};
+use tree_sitter::Node;
+
+/// Accumulates recognized renders into a class → method → render symbol tree.
+///
+/// Classes and methods are keyed by their tree-sitter node id so multiple
+/// renders in the same method nest under a single symbol, preserving source
+/// order. Renders outside any class or method are surfaced at the top level.
+#[derive(Default)]
+pub struct SymbolTree {
+    classes: Vec<SymbolGroup>,
+    /// Renders that sit directly at file scope, under no class or method.
+    loose: Vec<DocumentSymbol>,
+}
+
+/// One declaration (class or method) and the renders gathered beneath it.
+struct SymbolGroup {
+    id: usize,
+    symbol: DocumentSymbol,
+    children: Vec<SymbolGroup>,
+    renders: Vec<DocumentSymbol>,
+}
+
+impl SymbolTree {
+    /// Place `render` under its enclosing class and method, creating the group
+    /// symbols on first sight.
+    pub fn insert(
+        &mut self,
+        class: Option<DeclarationSymbol>,
+        method: Option<DeclarationSymbol>,
+        render: DocumentSymbol,
+    ) {
+        match class {
+            Some(class) => {
+                let class_group = Self::group_for(&mut self.classes, class);
+                match method {
+                    Some(method) => Self::group_for(&mut class_group.children, method)
+                        .renders
+                        .push(render),
+                    None => class_group.renders.push(render),
+                }
+            }
+            None => match method {
+                Some(method) => Self::group_for(&mut self.classes, method).renders.push(render),
+                None => self.loose.push(render),
+            },
+        }
+    }
+
+    /// Find or create the group for `declaration` within `groups`.
+    fn group_for(groups: &mut Vec<SymbolGroup>, declaration: DeclarationSymbol) -> &mut SymbolGroup {
+        if let Some(index) = groups.iter().position(|group| group.id == declaration.id) {
+            return &mut groups[index];
+        }
+        groups.push(SymbolGroup {
+            id: declaration.id,
+            symbol: declaration.symbol,
+            children: Vec::new(),
+            renders: Vec::new(),
+        });
+        groups.last_mut().expect("just pushed")
+    }
+
+    /// Collapse the accumulated groups into nested [`DocumentSymbol`]s.
+    pub fn finish(self) -> Vec<DocumentSymbol> {
+        let mut symbols: Vec<DocumentSymbol> =
+            self.classes.into_iter().map(SymbolGroup::into_symbol).collect();
+        symbols.extend(self.loose);
+        symbols
+    }
+}
+
+impl SymbolGroup {
+    fn into_symbol(self) -> DocumentSymbol {
+        let mut children: Vec<DocumentSymbol> =
+            self.children.into_iter().map(SymbolGroup::into_symbol).collect();
+        children.extend(self.renders);
+
+        let mut symbol = self.symbol;
+        symbol.children = (!children.is_empty()).then_some(children);
+        symbol
+    }
+}
+
+/// A class or method declaration captured as a symbol, keyed by its node id.
+pub struct DeclarationSymbol {
+    id: usize,
+    symbol: DocumentSymbol,
+}
+
+/// The nearest ancestor of `node` whose kind is in `kinds`, captured as a
+/// [`DeclarationSymbol`] labeled with its declared name.
+pub fn enclosing_declaration(
+    node: Node,
+    kinds: &[&str],
+    text: &str,
+) -> Option<DeclarationSymbol> {
+    let mut current = node.parent();
+    while let Some(declaration) = current {
+        if kinds.contains(&declaration.kind()) {
+            let name_node = declaration.child_by_field_name("name")?;
+            let name = AstTraversal::extract_node_text(&name_node, text).ok()?;
+            let kind = if declaration.kind() == "class_declaration"
+                || declaration.kind() == "enum_declaration"
+                || declaration.kind() == "trait_declaration"
+            {
+                SymbolKind::CLASS
+            } else {
+                SymbolKind::METHOD
+            };
+
+            #[allow(deprecated)]
+            let symbol = DocumentSymbol {
+                name,
+                detail: None,
+                kind,
+                tags: None,
+                deprecated: None,
+                range: AstTraversal::node_range(&declaration),
+                selection_range: AstTraversal::node_range(&name_node),
+                children: None,
+            };
+            return Some(DeclarationSymbol {
+                id: declaration.id(),
+                symbol,
+            });
+        }
+        current = declaration.parent();
+    }
+    None
+}
@@ -0,0 +1,208 @@
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use crate::view_intelligence::helpers::{normalize_path, path_argument_node, position_at};
+use crate::view_intelligence::import_analyzer::ImportAnalyzer;
+use crate::view_intelligence::path_resolver::TemplatePathResolver;
+use crate::view_intelligence::query::ViewQuery;
+use crate::view_intelligence::types::RendererRegistry;
+use lsp_types::Range;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Node, Tree};
+
+/// A nested template reference discovered inside a `.view.php` file: the source
+/// range of the reference and the template file it resolves to.
+pub struct TemplateDependency {
+    pub range: Range,
+    pub target: PathBuf,
+}
+
+/// Parses a resolved `.view.php` template for the other templates it embeds.
+///
+/// Two reference forms are recognized: PHP-level renders inside the template
+/// (`view(...)`, `include`/`require`) and `<x-component>` tags in the markup.
+/// The resolved targets seed the template dependency graph and back
+/// go-to-definition from a partial reference to its defining file.
+pub struct TemplateAnalyzer;
+
+impl TemplateAnalyzer {
+    /// Every nested template reference in `text`, resolved against the template
+    /// file at `template_path`.
+    pub fn dependencies(
+        tree: &Tree,
+        text: &str,
+        template_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Vec<TemplateDependency> {
+        let mut dependencies = Vec::new();
+        Self::collect_php_renders(tree, text, template_path, workspace_root, &mut dependencies);
+        Self::collect_component_tags(text, template_path, workspace_root, &mut dependencies);
+        dependencies
+    }
+
+    /// Collect `view(...)` and `include`/`require` renders embedded in the
+    /// template's PHP.
+    fn collect_php_renders(
+        tree: &Tree,
+        text: &str,
+        template_path: &Path,
+        workspace_root: Option<&Path>,
+        dependencies: &mut Vec<TemplateDependency>,
+    ) {
+        let registry = RendererRegistry::default();
+        let query = ViewQuery::run(tree, text);
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        for call in &query.calls {
+            if registry.recognize(call, &imports, text).is_none() {
+                continue;
+            }
+            if let Some(argument) = path_argument_node(call.arguments) {
+                if let Some(target) =
+                    TemplatePathResolver::resolve_node(&argument, text, template_path, workspace_root)
+                {
+                    dependencies.push(TemplateDependency {
+                        range: AstTraversal::node_range(&argument),
+                        target,
+                    });
+                }
+            }
+        }
+
+        Self::visit(&tree.root_node(), &mut |node| {
+            if !matches!(
+                node.kind(),
+                "include_expression"
+                    | "include_once_expression"
+                    | "require_expression"
+                    | "require_once_expression"
+            ) {
+                return;
+            }
+            // The single operand of an include/require is its last child.
+            let Some(argument) = node.child(node.child_count().saturating_sub(1)) else {
+                return;
+            };
+            if let Some(target) =
+                TemplatePathResolver::resolve_node(&argument, text, template_path, workspace_root)
+            {
+                dependencies.push(TemplateDependency {
+                    range: AstTraversal::node_range(&argument),
+                    target,
+                });
+            }
+        });
+    }
+
+    /// Collect `<x-component>` tags from the template markup.
+    ///
+    /// The search is scoped to text outside `<?php ... ?>` blocks, so an
+    /// occurrence of `<x-` inside a PHP string literal or comment isn't
+    /// mistaken for a real component tag.
+    pub fn collect_component_tags(
+        text: &str,
+        template_path: &Path,
+        workspace_root: Option<&Path>,
+        dependencies: &mut Vec<TemplateDependency>,
+    ) {
+        let bytes = text.as_bytes();
+        let php_blocks = Self::php_block_ranges(text);
+        let mut search = 0;
+        while let Some(relative) = text[search..].find("<x-") {
+            let start = search + relative;
+            let name_start = start + "<x-".len();
+            let mut end = name_start;
+            while end < bytes.len() && Self::is_component_name_byte(bytes[end]) {
+                end += 1;
+            }
+            search = end;
+
+            if php_blocks.iter().any(|block| block.contains(&start)) {
+                continue;
+            }
+
+            let name = &text[name_start..end];
+            if name.is_empty() {
+                continue;
+            }
+            if let Some(target) = Self::resolve_component(name, template_path, workspace_root) {
+                dependencies.push(TemplateDependency {
+                    range: Range::new(position_at(text, start), position_at(text, end)),
+                    target,
+                });
+            }
+        }
+    }
+
+    /// Byte ranges of every `<?php`/`<?=`/`<?` ... `?>` block in `text`, used
+    /// to keep markup-only scans (like component tag detection) from matching
+    /// inside embedded PHP code.
+    pub fn php_block_ranges(text: &str) -> Vec<std::ops::Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut search = 0;
+        while let Some(relative) = text[search..].find("<?") {
+            let start = search + relative;
+            let after_open = start + "<?".len();
+            let end = text[after_open..]
+                .find("?>")
+                .map(|relative_close| after_open + relative_close + "?>".len())
+                .unwrap_or(text.len());
+            ranges.push(start..end);
+            search = end;
+        }
+        ranges
+    }
+
+    /// Whether `byte` can appear in a component tag name (`x-post.meta`).
+    fn is_component_name_byte(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.')
+    }
+
+    /// Resolve a component tag name to its defining `.view.php` file.
+    ///
+    /// Dots separate directory segments (`x-layout.base` -> `layout/base`). The
+    /// name is looked up both bare and with the `x-` filename prefix Tempest uses
+    /// for file-based components, relative to the template's own directory and
+    /// then to the workspace root.
+    fn resolve_component(
+        name: &str,
+        template_path: &Path,
+        workspace_root: Option<&Path>,
+    ) -> Option<PathBuf> {
+        let relative = name.replace('.', "/");
+        let mut bases = Vec::new();
+        if let Some(parent) = template_path.parent() {
+            bases.push(parent.to_path_buf());
+        }
+        if let Some(root) = workspace_root {
+            bases.push(root.to_path_buf());
+        }
+
+        for base in bases {
+            for file in [
+                format!("{relative}.view.php"),
+                format!("x-{relative}.view.php"),
+            ] {
+                let candidate = normalize_path(&base.join(&file));
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Depth-first visit of every node under `node`.
+    fn visit<F: FnMut(&Node)>(node: &Node, callback: &mut F) {
+        callback(node);
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            loop {
+                Self::visit(&cursor.node(), callback);
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+    }
+}
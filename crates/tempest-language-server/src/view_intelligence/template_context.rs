@@ -0,0 +1,126 @@
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use crate::view_intelligence::helpers::is_superglobal;
+use std::collections::HashSet;
+use std::path::Path;
+use tempest_php_parser::PhpParser;
+use tree_sitter::Node;
+
+/// Reads the variables a `.view.php` template consumes from its render context.
+pub struct TemplateContext;
+
+impl TemplateContext {
+    /// Parse `template` and return the set of variables it reads and never
+    /// binds locally anywhere in the file; a name that is bound at any point is
+    /// excluded even where it is read before the binding. `$this`,
+    /// superglobals, and every locally bound name —
+    /// assignment and loop targets, function/closure/arrow parameters, closure
+    /// `use` captures, `catch` variables, and `global`/`static` declarations —
+    /// are excluded.
+    pub fn consumed_variables(template: &Path) -> Option<HashSet<String>> {
+        let source = std::fs::read_to_string(template).ok()?;
+        let parser = PhpParser::new().ok()?;
+        let tree = parser.parse(&source, None).ok()?;
+
+        let mut read = HashSet::new();
+        let mut assigned = HashSet::new();
+        Self::walk(&tree.root_node(), &source, &mut read, &mut assigned);
+
+        read.retain(|name| {
+            name != "this" && !is_superglobal(name) && !assigned.contains(name)
+        });
+        Some(read)
+    }
+
+    fn walk(node: &Node, text: &str, read: &mut HashSet<String>, assigned: &mut HashSet<String>) {
+        match node.kind() {
+            "assignment_expression" => {
+                if let Some(left) = node.child_by_field_name("left") {
+                    Self::mark_assigned(&left, text, assigned);
+                }
+            }
+            // Parameters of functions, methods, closures and arrow functions
+            // bind locals rather than reading context. Every such form nests its
+            // parameters under a `formal_parameters` node, so marking that whole
+            // sub-tree assigned keeps the bound names out of `read` (default
+            // values may only be constant expressions, never variable reads).
+            "formal_parameters" => {
+                Self::mark_assigned(node, text, assigned);
+            }
+            // `use ($captured)` clauses of a closure, `global $x;` statements and
+            // `static $x;` declarations all introduce locals, not context reads.
+            "anonymous_function_use_clause"
+            | "global_declaration"
+            | "function_static_declaration" => {
+                Self::mark_assigned(node, text, assigned);
+            }
+            // `catch (Throwable $e)` binds the exception variable locally; only
+            // the header variable is a binding, so the body is left to recurse.
+            "catch_clause" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if child.kind() == "variable_name" {
+                        Self::mark_assigned(&child, text, assigned);
+                    }
+                }
+            }
+            "foreach_statement" => {
+                // Variables between `as` and the closing `)` are loop bindings,
+                // not context reads. The collection expression before `as` and
+                // the body after `)` are handled by the normal recursion.
+                if let (Some(as_end), Some(rparen_start)) =
+                    (Self::as_end(node), Self::rparen_start(node))
+                {
+                    let mut cursor = node.walk();
+                    for child in node.children(&mut cursor) {
+                        if child.start_byte() >= as_end && child.end_byte() <= rparen_start {
+                            Self::mark_assigned(&child, text, assigned);
+                        }
+                    }
+                }
+            }
+            "variable_name" => {
+                if let Ok(raw) = AstTraversal::extract_node_text(node, text) {
+                    read.insert(raw.trim_start_matches('$').to_string());
+                }
+            }
+            _ => {}
+        }
+
+        AstTraversal::traverse_children(node, |child| {
+            Self::walk(&child, text, read, assigned);
+        });
+    }
+
+    /// End byte of the `as` keyword inside a `foreach` header, if present.
+    fn as_end(node: &Node) -> Option<usize> {
+        let mut cursor = node.walk();
+        let end_byte = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == "as")
+            .map(|child| child.end_byte());
+        end_byte
+    }
+
+    /// Start byte of the `)` closing a `foreach` header, if present.
+    fn rparen_start(node: &Node) -> Option<usize> {
+        let mut cursor = node.walk();
+        let start_byte = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == ")")
+            .map(|child| child.start_byte());
+        start_byte
+    }
+
+    /// Record every `variable_name` within `node` as an assignment target.
+    fn mark_assigned(node: &Node, text: &str, assigned: &mut HashSet<String>) {
+        if node.kind() == "variable_name" {
+            if let Ok(raw) = AstTraversal::extract_node_text(node, text) {
+                assigned.insert(raw.trim_start_matches('$').to_string());
+            }
+            return;
+        }
+        AstTraversal::traverse_children(node, |child| {
+            Self::mark_assigned(&child, text, assigned);
+        });
+    }
+}
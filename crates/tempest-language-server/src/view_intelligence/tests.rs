@@ -0,0 +1,789 @@
+use super::*;
+use crate::view_intelligence::helpers::normalize_path;
+use crate::view_intelligence::types::{Result, ViewAnalysisError, ViewCall};
+use lsp_types::DiagnosticSeverity;
+use std::collections::HashMap;
+use tempest_php_parser::PhpParser;
+
+fn parse_php_code(code: &str) -> Result<Tree> {
+    let parser = PhpParser::new().map_err(|e| ViewAnalysisError::ParseError(e.to_string()))?;
+    parser
+        .parse(code, None)
+        .map_err(|e| ViewAnalysisError::ParseError(e.to_string()))
+}
+
+fn analyze_imports_sync(code: &str) -> Result<HashMap<String, ViewImportType>> {
+    let tree = parse_php_code(code)?;
+    ImportAnalyzer::analyze_imports(&tree, code)
+}
+
+fn analyze_calls_sync(code: &str) -> Result<Vec<ViewCall>> {
+    let tree = parse_php_code(code)?;
+    FunctionCallAnalyzer::find_function_calls(&tree, code)
+}
+#[test]
+fn test_direct_namespace_calls_work() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return Tempest\view(__DIR__ . '/../Views/home.view.php');
+    }
+
+    public function other(): View
+    {
+        return \Tempest\view(__DIR__ . '/../Views/other.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let calls = analyze_calls_sync(code).unwrap();
+
+    assert!(imports.contains_key("Tempest\\view"));
+    assert!(imports.contains_key("\\Tempest\\view"));
+
+    assert_eq!(calls.len(), 2);
+    assert_eq!(calls[0].function_name, "Tempest\\view");
+    assert_eq!(calls[1].function_name, "\\Tempest\\view");
+
+    assert_eq!(calls[0].parameters.len(), 1);
+    assert_eq!(calls[1].parameters.len(), 1);
+}
+
+#[test]
+fn test_simple_function_import() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+use function Tempest\view;
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return view(__DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let calls = analyze_calls_sync(code).unwrap();
+
+    assert!(imports.contains_key("view"));
+    assert_eq!(
+        imports.get("view").unwrap(),
+        &ViewImportType::FunctionImport
+    );
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function_name, "view");
+}
+
+#[test]
+fn test_grouped_function_import() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+use function Tempest\{root_path, view};
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return view(__DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let calls = analyze_calls_sync(code).unwrap();
+
+    assert!(imports.contains_key("view"));
+    assert_eq!(
+        imports.get("view").unwrap(),
+        &ViewImportType::FunctionImport
+    );
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function_name, "view");
+}
+
+#[test]
+fn test_aliased_function_import() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+use function Tempest\view as SomeMethod;
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return SomeMethod(__DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let calls = analyze_calls_sync(code).unwrap();
+
+    assert!(imports.contains_key("SomeMethod"));
+    assert_eq!(
+        imports.get("SomeMethod").unwrap(),
+        &ViewImportType::FunctionImportWithAlias("SomeMethod".to_string())
+    );
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function_name, "SomeMethod");
+}
+
+#[test]
+fn test_grouped_import_without_view() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+use function Tempest\{root_path, helper};
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return view(__DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let calls = analyze_calls_sync(code).unwrap();
+
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].function_name, "view");
+
+    let filtered_calls: Vec<_> = calls
+        .into_iter()
+        .filter(|call| imports.contains_key(&call.function_name))
+        .collect();
+
+    assert_eq!(filtered_calls.len(), 0);
+}
+
+#[test]
+fn test_import_code_action_ignores_similarly_named_namespace() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use function Acme\TempestUtils\{bar, foo};
+
+final readonly class HomeController
+{
+    public function __invoke()
+    {
+        return \Tempest\view(__DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let tree = parse_php_code(code).unwrap();
+    let name_range = analyze_calls_sync(code).unwrap()[0].name_range;
+    let actions = ViewIntelligence::code_actions(&tree, code, name_range);
+
+    assert_eq!(actions.len(), 1);
+    let import_edit = actions[0]
+        .edits
+        .iter()
+        .find(|edit| edit.new_text.contains("use function"))
+        .expect("a fresh import edit, not a rewrite of the Acme\\TempestUtils group");
+    assert_eq!(import_edit.new_text, "\nuse function Tempest\\view;");
+}
+
+#[test]
+fn test_mixed_imports_and_calls() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+use function Tempest\view;
+use function Tempest\view as render;
+
+final readonly class HomeController
+{
+    public function one(): View
+    {
+        return view(__DIR__ . '/../Views/one.view.php');
+    }
+
+    public function two(): View
+    {
+        return render(__DIR__ . '/../Views/two.view.php');
+    }
+
+    public function three(): View
+    {
+        return Tempest\view(__DIR__ . '/../Views/three.view.php');
+    }
+
+    public function four(): View
+    {
+        return \Tempest\view(__DIR__ . '/../Views/four.view.php');
+    }
+}"#;
+
+    let imports = analyze_imports_sync(code).unwrap();
+    let mut calls = analyze_calls_sync(code).unwrap();
+
+    assert!(imports.contains_key("view"));
+    assert!(imports.contains_key("render"));
+    assert!(imports.contains_key("Tempest\\view"));
+    assert!(imports.contains_key("\\Tempest\\view"));
+
+    calls.retain(|call| imports.contains_key(&call.function_name));
+
+    assert_eq!(calls.len(), 4);
+
+    let call_names: Vec<&str> = calls.iter().map(|c| c.function_name.as_str()).collect();
+    assert!(call_names.contains(&"view"));
+    assert!(call_names.contains(&"render"));
+    assert!(call_names.contains(&"Tempest\\view"));
+    assert!(call_names.contains(&"\\Tempest\\view"));
+}
+
+#[test]
+fn test_parameter_parsing() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use function Tempest\view;
+
+final readonly class HomeController
+{
+    public function simple(): View
+    {
+        return view('template.view.php');
+    }
+
+    public function withData(): View
+    {
+        return view('template.view.php', ['key' => 'value']);
+    }
+
+    public function complex(): View
+    {
+        return view(
+            __DIR__ . '/../Views/home.view.php',
+            $this->getData(),
+            $options
+        );
+    }
+}"#;
+
+    let calls = analyze_calls_sync(code).unwrap();
+
+    let view_calls: Vec<_> = calls
+        .into_iter()
+        .filter(|call| call.function_name == "view")
+        .collect();
+
+    assert_eq!(view_calls.len(), 3);
+
+    assert_eq!(view_calls[0].parameters.len(), 1);
+    assert_eq!(view_calls[0].parameters[0].value, "'template.view.php'");
+    assert!(view_calls[0].parameters[0].name.is_none());
+
+    assert_eq!(view_calls[1].parameters.len(), 2);
+    assert_eq!(view_calls[1].parameters[0].value, "'template.view.php'");
+    assert_eq!(view_calls[1].parameters[1].value, "['key' => 'value']");
+
+    assert_eq!(view_calls[2].parameters.len(), 3);
+    assert_eq!(
+        view_calls[2].parameters[0].value,
+        "__DIR__ . '/../Views/home.view.php'"
+    );
+    assert_eq!(view_calls[2].parameters[1].value, "$this->getData()");
+    assert_eq!(view_calls[2].parameters[2].value, "$options");
+}
+
+#[test]
+fn test_named_parameter_parsing() {
+    let code = r#"<?php
+namespace Happytodev\Cyclone\Controllers;
+
+use Tempest\View\View;
+use function Tempest\{root_path, view};
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        return view(path: __DIR__ . '/../Views/home.view.php');
+    }
+}"#;
+
+    let calls = analyze_calls_sync(code).unwrap();
+
+    let view_calls: Vec<_> = calls
+        .into_iter()
+        .filter(|call| call.function_name == "view")
+        .collect();
+
+    assert_eq!(view_calls.len(), 1);
+    assert_eq!(view_calls[0].parameters.len(), 1);
+
+    assert_eq!(view_calls[0].parameters[0].name, Some("path".to_string()));
+    assert_eq!(
+        view_calls[0].parameters[0].value,
+        "__DIR__ . '/../Views/home.view.php'"
+    );
+    assert_eq!(
+        view_calls[0].parameters[0].raw_text,
+        "path: __DIR__ . '/../Views/home.view.php'"
+    );
+}
+
+#[test]
+fn test_method_and_static_calls_are_captured() {
+    let code = r#"<?php
+namespace My\Namespace\Controllers;
+
+use Tempest\View\View;
+
+final readonly class HomeController
+{
+    public function __invoke(): View
+    {
+        $this->view('home.view.php');
+        return View::create('other.view.php');
+    }
+}"#;
+
+    let calls = analyze_calls_sync(code).unwrap();
+
+    let method = calls
+        .iter()
+        .find(|call| call.function_name == "view")
+        .expect("method call captured");
+    assert_eq!(method.parameters.len(), 1);
+    assert_eq!(method.parameters[0].value, "'home.view.php'");
+
+    let static_call = calls
+        .iter()
+        .find(|call| call.function_name == "create")
+        .expect("static call captured");
+    assert_eq!(static_call.parameters.len(), 1);
+    assert_eq!(static_call.parameters[0].value, "'other.view.php'");
+}
+
+#[test]
+fn test_registry_recognizes_configured_selectors() {
+    let settings = serde_json::json!({
+        "viewRenderers": {
+            "methods": ["render"],
+            "statics": ["View::create"]
+        }
+    });
+    let registry = RendererRegistry::from_settings(&settings);
+
+    let code = r#"<?php
+final class C
+{
+    public function __invoke(): void
+    {
+        $this->render('home.view.php');
+        $this->view('home.view.php');
+        View::create('other.view.php');
+    }
+}"#;
+    let tree = parse_php_code(code).unwrap();
+    let query = ViewQuery::run(&tree, code);
+    let imports = HashMap::new();
+    let selectors: Vec<_> = query
+        .calls
+        .iter()
+        .filter_map(|call| registry.recognize(call, &imports, code))
+        .collect();
+
+    // `render` and `View::create` are configured; the default `view`
+    // method was replaced by the explicit list and is no longer matched.
+    assert!(selectors.contains(&"render".to_string()));
+    assert!(selectors.contains(&"View::create".to_string()));
+    assert!(!selectors.contains(&"view".to_string()));
+}
+
+#[test]
+fn test_document_symbols_nest_under_class_and_method() {
+    let code = r#"<?php
+namespace App;
+
+use function Tempest\view;
+
+final class HomeController
+{
+    public function __invoke(): View
+    {
+        return view('home.view.php');
+    }
+}"#;
+    let tree = parse_php_code(code).unwrap();
+    let symbols = ViewIntelligence::document_symbols(
+        &tree,
+        code,
+        Path::new("/app/HomeController.php"),
+        &RendererRegistry::default(),
+    );
+
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "HomeController");
+
+    let methods = symbols[0].children.as_ref().expect("class has children");
+    assert_eq!(methods.len(), 1);
+    assert_eq!(methods[0].name, "__invoke");
+
+    let renders = methods[0].children.as_ref().expect("method has renders");
+    assert_eq!(renders.len(), 1);
+    assert!(renders[0].name.ends_with("home.view.php"));
+}
+
+#[test]
+fn test_view_render_lenses_flag_dynamic_paths() {
+    let code = r#"<?php
+
+use function Tempest\view;
+
+$name = 'home';
+view($name);
+view('static.view.php');
+"#;
+    let tree = parse_php_code(code).unwrap();
+    let lenses = ViewIntelligence::view_render_lenses(
+        &tree,
+        code,
+        Path::new("/app/x.php"),
+        &RendererRegistry::default(),
+    );
+
+    assert_eq!(lenses.len(), 2);
+    assert_eq!(lenses.iter().filter(|lens| lens.target.is_none()).count(), 1);
+    assert_eq!(lenses.iter().filter(|lens| lens.target.is_some()).count(), 1);
+}
+
+#[test]
+fn test_partially_dynamic_path_hints_instead_of_erroring() {
+    let code = r#"<?php
+
+use function Tempest\view;
+
+view($section . '/detail.view.php');
+"#;
+    let tree = parse_php_code(code).unwrap();
+    let diagnostics = ViewIntelligence::diagnostics(
+        &tree,
+        code,
+        Some(Path::new("/app/x.php")),
+        None,
+        &RendererRegistry::default(),
+        &TemplateIndex::new(),
+    );
+
+    // The constant fragment must not be promoted to a guessed path: the
+    // expression is unresolvable, so it gets a hint rather than a spurious
+    // "template not found" error.
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.severity != Some(DiagnosticSeverity::ERROR)));
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.severity == Some(DiagnosticSeverity::HINT)));
+}
+
+#[test]
+fn test_partially_dynamic_path_has_no_link_or_definition() {
+    let code = r#"<?php
+
+use function Tempest\view;
+
+view($section . '/detail.view.php');
+"#;
+    let tree = parse_php_code(code).unwrap();
+    let document_path = Path::new("/app/x.php");
+    let registry = RendererRegistry::default();
+
+    // Navigation and document links only ever point at a fully resolved
+    // path, never at a guess assembled from the constant fragments.
+    let links =
+        ViewIntelligence::document_links(&tree, code, document_path, None, &registry);
+    assert!(links.is_empty());
+
+    let offset = code.find("$section").unwrap();
+    let target = ViewIntelligence::resolve_definition(
+        &tree,
+        code,
+        offset,
+        document_path,
+        None,
+        &registry,
+    );
+    assert!(target.is_none());
+}
+
+/// A unique scratch directory for a test that needs template files on
+/// disk; `consumed_variables` reads the rendered template, so the
+/// view-data checks can only be exercised against real files.
+fn scratch_dir(label: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!(
+        "tempest-ls-{label}-{}-{seq}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn uri_for(path: &Path) -> lsp_types::Uri {
+    format!("file://{}", path.display()).parse().unwrap()
+}
+
+#[test]
+fn test_view_data_flags_undefined_and_unused_keys() {
+    let dir = scratch_dir("view-data");
+    std::fs::write(
+        dir.join("profile.view.php"),
+        "<?php\necho $title;\necho $missing;\n",
+    )
+    .unwrap();
+
+    let controller = dir.join("Controller.php");
+    let code = r#"<?php
+
+use function Tempest\view;
+
+view('profile.view.php', ['title' => 'Hi', 'extra' => 'x']);
+"#;
+    let tree = parse_php_code(code).unwrap();
+    let registry = RendererRegistry::default();
+
+    let templates = TemplateIndex::new();
+    templates.update(
+        &uri_for(&controller),
+        ViewIntelligence::view_render_sites(&tree, code, &controller, None, &registry),
+    );
+
+    let diagnostics = ViewIntelligence::diagnostics(
+        &tree,
+        code,
+        Some(&controller),
+        None,
+        &registry,
+        &templates,
+    );
+
+    // `$missing` is read by the template but no call supplies it.
+    assert!(diagnostics.iter().any(|d| {
+        d.severity == Some(DiagnosticSeverity::WARNING) && d.message.contains("$missing")
+    }));
+    // `$title` is provided, so it is not flagged.
+    assert!(!diagnostics.iter().any(|d| d.message.contains("$title")));
+    // `extra` is provided but never read by the template.
+    assert!(diagnostics.iter().any(|d| {
+        d.severity == Some(DiagnosticSeverity::HINT) && d.message.contains("`extra`")
+    }));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_view_data_suppressed_without_a_data_source() {
+    let dir = scratch_dir("no-data");
+    std::fs::write(dir.join("home.view.php"), "<?php\necho $user;\n").unwrap();
+
+    let controller = dir.join("Controller.php");
+    // No data argument: Tempest may attach `$user` through a fluent setter
+    // or controller context, so nothing can be reported missing.
+    let code = r#"<?php
+
+use function Tempest\view;
+
+view('home.view.php');
+"#;
+    let tree = parse_php_code(code).unwrap();
+    let registry = RendererRegistry::default();
+
+    let templates = TemplateIndex::new();
+    templates.update(
+        &uri_for(&controller),
+        ViewIntelligence::view_render_sites(&tree, code, &controller, None, &registry),
+    );
+
+    let diagnostics = ViewIntelligence::diagnostics(
+        &tree,
+        code,
+        Some(&controller),
+        None,
+        &registry,
+        &templates,
+    );
+
+    assert!(!diagnostics.iter().any(|d| d.message.contains("$user")));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_view_data_aggregates_keys_across_call_sites() {
+    let dir = scratch_dir("aggregate");
+    std::fs::write(
+        dir.join("shared.view.php"),
+        "<?php\necho $title;\necho $ghost;\n",
+    )
+    .unwrap();
+
+    let registry = RendererRegistry::default();
+    let templates = TemplateIndex::new();
+
+    // One call site provides `title` but not the document under analysis.
+    let other = dir.join("Other.php");
+    let other_code = r#"<?php
+
+use function Tempest\view;
+
+view('shared.view.php', ['title' => 'x']);
+"#;
+    let other_tree = parse_php_code(other_code).unwrap();
+    templates.update(
+        &uri_for(&other),
+        ViewIntelligence::view_render_sites(&other_tree, other_code, &other, None, &registry),
+    );
+
+    // The document being diagnosed supplies only `other`.
+    let controller = dir.join("Controller.php");
+    let code = r#"<?php
+
+use function Tempest\view;
+
+view('shared.view.php', ['other' => 'y']);
+"#;
+    let tree = parse_php_code(code).unwrap();
+    templates.update(
+        &uri_for(&controller),
+        ViewIntelligence::view_render_sites(&tree, code, &controller, None, &registry),
+    );
+
+    let diagnostics = ViewIntelligence::diagnostics(
+        &tree,
+        code,
+        Some(&controller),
+        None,
+        &registry,
+        &templates,
+    );
+
+    // `$title` is supplied by the other call site, so the aggregate covers
+    // it even though this call does not.
+    assert!(!diagnostics.iter().any(|d| d.message.contains("$title")));
+    // `$ghost` is read but supplied by no call site anywhere.
+    assert!(diagnostics.iter().any(|d| {
+        d.severity == Some(DiagnosticSeverity::WARNING) && d.message.contains("$ghost")
+    }));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_component_tags_ignore_occurrences_inside_php_blocks() {
+    let dir = scratch_dir("component-tags");
+    let page = dir.join("page.view.php");
+    std::fs::write(dir.join("x-card.view.php"), "<div>card</div>").unwrap();
+
+    let code = r#"<div>
+<?php // a mention of <x-card /> in a comment is not a real embed ?>
+    <x-card/>
+</div>
+"#;
+    std::fs::write(&page, code).unwrap();
+    let tree = parse_php_code(code).unwrap();
+
+    let dependencies = TemplateAnalyzer::dependencies(&tree, code, &page, Some(&dir));
+
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(
+        dependencies[0].target,
+        normalize_path(&dir.join("x-card.view.php"))
+    );
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_component_tag_scanner_skips_php_blocks() {
+    let text = "<div><?php echo '<x-card />'; ?><x-real/></div>";
+    let fake_start = text.find("<x-card").unwrap();
+    let real_start = text.find("<x-real").unwrap();
+
+    let mut dependencies = Vec::new();
+    TemplateAnalyzer::collect_component_tags(
+        text,
+        Path::new("/templates/page.view.php"),
+        None,
+        &mut dependencies,
+    );
+
+    // Neither tag resolves to a file on disk, but the scanner must at
+    // least have skipped the one embedded in the PHP string: confirmed by
+    // checking the PHP-block ranges directly rather than a file lookup.
+    let php_blocks = TemplateAnalyzer::php_block_ranges(text);
+    assert!(php_blocks.iter().any(|block| block.contains(&fake_start)));
+    assert!(!php_blocks.iter().any(|block| block.contains(&real_start)));
+}
+
+#[test]
+fn test_directory_entries_filters_by_prefix_and_suffix() {
+    let dir = scratch_dir("directory-entries");
+    std::fs::write(dir.join("home.view.php"), "").unwrap();
+    std::fs::write(dir.join("home.php"), "").unwrap();
+    std::fs::create_dir(dir.join("home")).unwrap();
+    std::fs::write(dir.join("other.view.php"), "").unwrap();
+
+    let mut entries = ViewIntelligence::directory_entries(&dir, "home");
+    entries.sort();
+
+    assert_eq!(entries, vec!["home.view.php".to_string(), "home/".to_string()]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_directory_entries_empty_for_missing_directory() {
+    let entries =
+        ViewIntelligence::directory_entries(Path::new("/no/such/directory"), "home");
+    assert!(entries.is_empty());
+}
+
+#[test]
+fn test_path_completions_offers_matching_templates() {
+    let dir = scratch_dir("path-completions");
+    std::fs::write(dir.join("home.view.php"), "").unwrap();
+    std::fs::write(dir.join("other.view.php"), "").unwrap();
+
+    let document_path = dir.join("controller.php");
+    let code = "<?php\nuse function Tempest\\view;\nview(__DIR__ . '/ho');\n".to_string();
+    std::fs::write(&document_path, &code).unwrap();
+    let tree = parse_php_code(&code).unwrap();
+
+    let offset = code.rfind("ho").unwrap() + "ho".len();
+    let completions = ViewIntelligence::path_completions(
+        &tree,
+        &code,
+        offset,
+        &document_path,
+        None,
+        &RendererRegistry::default(),
+    );
+
+    assert_eq!(completions, vec!["home.view.php".to_string()]);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -1,4 +1,9 @@
+use crate::view_intelligence::query::CapturedCall;
+use crate::view_intelligence::query::CallKind;
+use lsp_types::{Range, TextEdit};
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum ViewAnalysisError {
@@ -23,13 +28,6 @@ impl std::error::Error for ViewAnalysisError {}
 
 pub type Result<T> = std::result::Result<T, ViewAnalysisError>;
 
-#[derive(Debug, Clone)]
-pub struct ImportInfo {
-    pub namespace: String,
-    pub function_name: String,
-    pub alias: Option<String>,
-}
-
 #[derive(Debug, Clone, PartialEq)]
 pub enum ViewImportType {
     DirectNamespace,
@@ -47,11 +45,98 @@ impl ViewImportType {
     }
 }
 
+/// Which method and static selectors should be treated as view renderers, in
+/// addition to the `use function Tempest\view` free functions.
+///
+/// Method calls (`$this->view(...)`) are keyed by method name; static calls
+/// (`View::create(...)`) by `Class::method`. The defaults cover Tempest's own
+/// helpers; projects with custom wrappers extend them through LS settings.
+#[derive(Debug, Clone)]
+pub struct RendererRegistry {
+    methods: HashSet<String>,
+    statics: HashSet<String>,
+}
+
+impl Default for RendererRegistry {
+    fn default() -> Self {
+        Self {
+            methods: HashSet::from(["view".to_string()]),
+            statics: HashSet::from(["View::create".to_string()]),
+        }
+    }
+}
+
+impl RendererRegistry {
+    /// Build a registry from the `tempest.viewRenderers` section of the LS
+    /// settings, falling back to the defaults when a list is absent.
+    ///
+    /// ```jsonc
+    /// { "viewRenderers": { "methods": ["view", "render"],
+    ///                      "statics": ["View::create"] } }
+    /// ```
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let section = settings.get("viewRenderers");
+        let mut registry = Self::default();
+
+        if let Some(methods) = section.and_then(|s| s.get("methods")).and_then(|v| v.as_array()) {
+            registry.methods = methods
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+        }
+
+        if let Some(statics) = section.and_then(|s| s.get("statics")).and_then(|v| v.as_array()) {
+            registry.statics = statics
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect();
+        }
+
+        registry
+    }
+
+    /// The normalized renderer name when `call` is a recognized view render.
+    ///
+    /// Free functions are matched against the document's `view` imports; method
+    /// and static calls against the configured selectors.
+    pub fn recognize(
+        &self,
+        call: &CapturedCall,
+        imports: &HashMap<String, ViewImportType>,
+        text: &str,
+    ) -> Option<String> {
+        let selector = call.selector(text)?;
+        match call.kind {
+            CallKind::Function => imports.contains_key(&selector).then_some(selector),
+            CallKind::Method => self.methods.contains(&selector).then_some(selector),
+            CallKind::Static => self.statics.contains(&selector).then_some(selector),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ViewParameter {
     pub name: Option<String>,
     pub value: String,
     pub raw_text: String,
+    pub range: Range,
+}
+
+/// Outcome of statically evaluating a view-path expression.
+///
+/// A `view()` call's first argument is an arbitrary PHP expression; only the
+/// subset built from string literals, `__DIR__`/`__FILE__`, `dirname(...)` and
+/// concatenation can be turned into a concrete file. Anything touching a
+/// variable or method call is left [`ResolvedPath::Unresolvable`] so handlers
+/// don't chase a guessed path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedPath {
+    /// The expression folded to a concrete, normalized filesystem path.
+    Resolved(PathBuf),
+    /// The expression contains a non-constant operand. `prefix` carries the
+    /// longest constant leading path (if any) so completion can still offer
+    /// directory entries.
+    Unresolvable { prefix: Option<PathBuf> },
 }
 
 #[derive(Debug, Clone)]
@@ -60,20 +145,33 @@ pub struct ViewCall {
     pub line: usize,
     pub text: String,
     pub parameters: Vec<ViewParameter>,
+    /// Range of the whole call expression.
+    pub range: Range,
+    /// Range of just the called function name.
+    pub name_range: Range,
+    /// Statically evaluated template path of the first argument.
+    pub resolved_path: ResolvedPath,
 }
 
 impl ViewCall {
+    #[allow(clippy::too_many_arguments)]
     pub fn with_parameters(
         function_name: String,
         line: usize,
         text: String,
         parameters: Vec<ViewParameter>,
+        range: Range,
+        name_range: Range,
+        resolved_path: ResolvedPath,
     ) -> Self {
         Self {
             function_name,
             line,
             text,
             parameters,
+            range,
+            name_range,
+            resolved_path,
         }
     }
 }
@@ -107,3 +205,16 @@ impl Default for ViewAnalysisResult {
         Self::new()
     }
 }
+
+/// A named workspace edit offered as an LSP code action.
+pub struct ViewCodeAction {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// A single `view()` render surfaced for a code lens: the call's range and the
+/// resolved template target, or `None` when the path expression is dynamic.
+pub struct ViewRenderLens {
+    pub range: Range,
+    pub target: Option<PathBuf>,
+}
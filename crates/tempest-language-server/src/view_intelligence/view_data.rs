@@ -0,0 +1,151 @@
+use crate::template_index::ProvidedData;
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use crate::view_intelligence::helpers::{is_literal, strip_quotes};
+use lsp_types::Range;
+use tree_sitter::Node;
+
+/// The data keys a `view()` call passes to its template, gathered from every
+/// positional array argument after the template path and any named arguments.
+pub struct ViewData {
+    /// Statically-known key names paired with the range to blame for each.
+    pub keys: Vec<(String, Range)>,
+    /// Whether every provided key could be enumerated (no spread, dynamic key,
+    /// or non-array data argument).
+    pub keys_complete: bool,
+    /// Whether any provided value is computed at runtime, which suppresses the
+    /// "unused key" diagnostic.
+    pub has_dynamic: bool,
+    /// Whether the call carries a statically-enumerable data source at all (a
+    /// named data argument or a positional data array). A call with no data
+    /// argument may still receive data through fluent setters or controller
+    /// context, so its key set can't be treated as complete.
+    has_data_source: bool,
+}
+
+impl ViewData {
+    pub fn provided(arguments: Option<Node>, text: &str) -> Self {
+        let mut data = Self {
+            keys: Vec::new(),
+            keys_complete: true,
+            has_dynamic: false,
+            has_data_source: false,
+        };
+
+        let Some(arguments) = arguments else {
+            // No argument list at all: the call provides nothing.
+            return data;
+        };
+
+        let mut positional = 0;
+        AstTraversal::traverse_children(&arguments, |arg| {
+            if arg.kind() != "argument" {
+                return;
+            }
+
+            if let Some(name_node) = arg.child_by_field_name("name") {
+                let Ok(name) = AstTraversal::extract_node_text(&name_node, text) else {
+                    return;
+                };
+                // The template path can also be passed as `path:`.
+                if name == "path" {
+                    return;
+                }
+                data.has_data_source = true;
+                if !Self::argument_value_is_static(arg, &name_node) {
+                    data.has_dynamic = true;
+                }
+                data.keys.push((name, AstTraversal::node_range(arg)));
+            } else {
+                let index = positional;
+                positional += 1;
+                // The first positional argument is the template path.
+                if index == 0 {
+                    return;
+                }
+                data.has_data_source = true;
+                data.collect_array_keys(arg, text);
+            }
+        });
+
+        data
+    }
+
+    /// Gather the keys of an associative-array data argument, updating the
+    /// completeness and dynamic flags for spreads and dynamic keys/values.
+    fn collect_array_keys(&mut self, argument: &Node, text: &str) {
+        let array = AstTraversal::find_child_by_kind(argument, "array_creation_expression");
+
+        let Some(array) = array else {
+            // A non-array data argument (e.g. `$this->getData()`): the provided
+            // keys can't be enumerated.
+            self.keys_complete = false;
+            self.has_dynamic = true;
+            return;
+        };
+
+        let mut cursor = array.walk();
+        for element in array.named_children(&mut cursor) {
+            if element.kind() != "array_element_initializer" {
+                continue;
+            }
+
+            // A spread element (`...$data`) hides an unknown set of keys.
+            if AstTraversal::extract_node_text(&element, text)
+                .map(|raw| raw.trim_start().starts_with("..."))
+                .unwrap_or(false)
+            {
+                self.keys_complete = false;
+                self.has_dynamic = true;
+                continue;
+            }
+
+            let keyed = element.child(1).map(|node| node.kind()) == Some("=>");
+            if !keyed {
+                // A positional value in the data array isn't a named key.
+                self.has_dynamic = true;
+                continue;
+            }
+
+            let Some(key) = element.child(0) else {
+                continue;
+            };
+            match key.kind() {
+                "string" | "encapsed_string" => {
+                    if let Ok(raw) = AstTraversal::extract_node_text(&key, text) {
+                        self.keys
+                            .push((strip_quotes(&raw).to_string(), AstTraversal::node_range(&element)));
+                    }
+                    if element.child(2).map(|value| !is_literal(&value)).unwrap_or(false) {
+                        self.has_dynamic = true;
+                    }
+                }
+                _ => {
+                    // A computed key means we can't know the full key set.
+                    self.keys_complete = false;
+                    self.has_dynamic = true;
+                }
+            }
+        }
+    }
+
+    /// Whether the value of a named argument is a plain literal.
+    fn argument_value_is_static(arg: &Node, name_node: &Node) -> bool {
+        let mut cursor = arg.walk();
+        for child in arg.named_children(&mut cursor) {
+            if &child == name_node {
+                continue;
+            }
+            return is_literal(&child);
+        }
+        true
+    }
+
+    /// Summarize this call's data for the reverse index: the statically-known
+    /// keys and whether they fully enumerate what the call provides.
+    pub fn into_provided(self) -> ProvidedData {
+        ProvidedData {
+            keys: self.keys.into_iter().map(|(key, _)| key).collect(),
+            enumerable: self.has_data_source && self.keys_complete,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::template_index::TemplateIndex;
+use crate::view_intelligence::ast_traversal::AstTraversal;
+use crate::view_intelligence::helpers::path_argument_node;
+use crate::view_intelligence::import_analyzer::ImportAnalyzer;
+use crate::view_intelligence::path_resolver::TemplatePathResolver;
+use crate::view_intelligence::query::{CallKind, CapturedCall, ViewQuery};
+use crate::view_intelligence::template_context::TemplateContext;
+use crate::view_intelligence::types::RendererRegistry;
+use crate::view_intelligence::view_data::ViewData;
+use lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::Tree;
+
+/// Turns the results of the view analysis into LSP diagnostics so problems
+/// surface as editor squiggles rather than only in the output log.
+pub struct ViewDiagnostics;
+
+impl ViewDiagnostics {
+    pub fn collect(
+        tree: &Tree,
+        text: &str,
+        document_path: Option<&Path>,
+        workspace_root: Option<&Path>,
+        registry: &RendererRegistry,
+        templates: &TemplateIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let query = ViewQuery::run(tree, text);
+
+        let imports =
+            ImportAnalyzer::analyze_imports_from(&query.use_declarations, text).unwrap_or_default();
+
+        // Names of imported view functions that are actually called, so we can
+        // flag the ones that are never used.
+        let mut used_imports: HashSet<String> = HashSet::new();
+
+        for call in &query.calls {
+            let Some(function_node) = call.function else {
+                continue;
+            };
+
+            let Some(function_name) = registry.recognize(call, &imports, text) else {
+                // A free function that looks like a view call but has no import
+                // in scope; method/static calls are only ever recognized through
+                // the registry, so they are left alone here.
+                if call.kind == CallKind::Function {
+                    if let Ok(name) = AstTraversal::extract_node_text(&function_node, text) {
+                        if Self::looks_like_view_call(&name) {
+                            diagnostics.push(Self::diagnostic(
+                                AstTraversal::node_range(&function_node),
+                                DiagnosticSeverity::WARNING,
+                                format!(
+                                    "`{name}()` looks like a Tempest view call but no matching `use function Tempest\\view;` import is in scope"
+                                ),
+                            ));
+                        }
+                    }
+                }
+                continue;
+            };
+
+            if call.kind == CallKind::Function {
+                used_imports.insert(function_name);
+            }
+
+            let Some(argument) = path_argument_node(call.arguments) else {
+                continue;
+            };
+            let argument_range = AstTraversal::node_range(&argument);
+
+            let Some(document_path) = document_path else {
+                continue;
+            };
+
+            // A partially-dynamic path (`$x . '/detail.view.php'`) is unresolvable
+            // just like the fully-dynamic `view($x)`: `resolve_candidate` only
+            // returns `Some` for a fully-resolved expression, so the "template
+            // not found" error can only ever fire there, with everything else
+            // falling through to the hint.
+            match TemplatePathResolver::resolve_candidate(
+                &argument,
+                text,
+                document_path,
+                workspace_root,
+            ) {
+                Some(candidate) if candidate.is_file() => {
+                    Self::check_view_data(
+                        &mut diagnostics,
+                        call,
+                        AstTraversal::node_range(&function_node),
+                        text,
+                        &candidate,
+                        templates,
+                    );
+                }
+                Some(candidate) => diagnostics.push(Self::diagnostic(
+                    argument_range,
+                    DiagnosticSeverity::ERROR,
+                    format!("Template not found: {}", candidate.display()),
+                )),
+                None => diagnostics.push(Self::diagnostic(
+                    argument_range,
+                    DiagnosticSeverity::HINT,
+                    "Template path cannot be statically resolved; go-to-definition is unavailable"
+                        .to_string(),
+                )),
+            }
+        }
+
+        let (located, malformed) =
+            ImportAnalyzer::located_view_imports(&query.use_declarations, text);
+
+        for (name, _import_type, range) in located {
+            if !used_imports.contains(&name) {
+                diagnostics.push(Self::diagnostic(
+                    range,
+                    DiagnosticSeverity::HINT,
+                    format!("Unused view import: `{name}`"),
+                ));
+            }
+        }
+
+        for (message, range) in malformed {
+            diagnostics.push(Self::diagnostic(
+                range,
+                DiagnosticSeverity::WARNING,
+                format!("Invalid view import: {message}"),
+            ));
+        }
+
+        diagnostics
+    }
+
+    fn diagnostic(range: Range, severity: DiagnosticSeverity, message: String) -> Diagnostic {
+        Diagnostic {
+            range,
+            severity: Some(severity),
+            source: Some("tempest".to_string()),
+            message,
+            ..Diagnostic::default()
+        }
+    }
+
+    /// Whether a called function name is a plausible Tempest `view()` call
+    /// (bare `view` or any `\Namespace\view`).
+    pub fn looks_like_view_call(function_name: &str) -> bool {
+        function_name == "view" || function_name.ends_with("\\view")
+    }
+
+    /// Cross-check the data a `view()` call passes against the variables its
+    /// template consumes.
+    ///
+    /// Flags template variables no call site provides ("undefined variable")
+    /// and, when this call's provided keys are fully known and static, keys the
+    /// template never reads ("unused key").
+    ///
+    /// The undefined check aggregates across every call site rendering the
+    /// template (via the reverse index): a variable is only flagged when the
+    /// full provided-key set is known — every call site enumerates its data —
+    /// and none of them supply it. A call that attaches data through fluent
+    /// setters or controller context provides no enumerable source, so it makes
+    /// the set unknowable and suppresses the check rather than reporting every
+    /// variable as missing.
+    ///
+    /// The unused-key check stays per-call: a dynamic value
+    /// (`'user' => $this->getUser()`) keeps the key known but suppresses the
+    /// unused hint for that call; a spread or a non-array data argument makes
+    /// the key set unknowable and suppresses it too.
+    fn check_view_data(
+        diagnostics: &mut Vec<Diagnostic>,
+        call: &CapturedCall,
+        name_range: Range,
+        text: &str,
+        template: &Path,
+        templates: &TemplateIndex,
+    ) {
+        let Some(variables) = TemplateContext::consumed_variables(template) else {
+            return;
+        };
+
+        if let Some(provided_keys) = templates.provided_keys(template) {
+            for variable in &variables {
+                if !provided_keys.contains(variable) {
+                    diagnostics.push(Self::diagnostic(
+                        name_range,
+                        DiagnosticSeverity::WARNING,
+                        format!(
+                            "Template `{}` uses `${variable}` but no `view()` call provides it",
+                            template.display()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let provided = ViewData::provided(call.arguments, text);
+        if provided.keys_complete && !provided.has_dynamic {
+            for (key, range) in &provided.keys {
+                if !variables.contains(key) {
+                    diagnostics.push(Self::diagnostic(
+                        *range,
+                        DiagnosticSeverity::HINT,
+                        format!(
+                            "View data key `{key}` is not used by template `{}`",
+                            template.display()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+}